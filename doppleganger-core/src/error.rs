@@ -0,0 +1,166 @@
+use std::{error::Error, fmt};
+
+/// The error of a fallible mirror that records where it failed.
+///
+/// Every failure keeps the path from the root of the source to the value that was
+/// rejected, such as `orders[3].items[0].price`. The container impls of
+/// [`TryMirror`](crate::TryMirror) and derived fallible mirrors without a
+/// `#[dg(error = ...)]` add the segments as the error travels up. Any [`Error`] converts
+/// into a `MirrorError` with an empty path.
+///
+/// Like `anyhow::Error`, `MirrorError` does not implement [`Error`] itself so that it can
+/// convert from every error type.
+#[derive(Debug)]
+pub struct MirrorError {
+    failures: Vec<Failure>,
+}
+
+/// One rejected value of a [`MirrorError`].
+#[derive(Debug)]
+pub struct Failure {
+    path: Vec<PathSegment>,
+    error: Box<dyn Error + Send + Sync>,
+}
+
+/// One step of the path to a rejected value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A struct field, `.price`
+    Field(&'static str),
+    /// A position in a sequence, `[3]`
+    Index(usize),
+    /// A map key in its `Debug` form, `["alice"]`
+    Key(String),
+}
+
+impl MirrorError {
+    pub fn new(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            failures: vec![Failure {
+                path: Vec::new(),
+                error: error.into(),
+            }],
+        }
+    }
+
+    /// Every rejected value, more than one when the failures were collected.
+    pub fn failures(&self) -> &[Failure] {
+        &self.failures
+    }
+
+    /// Record that the failures happened inside the field `name`.
+    pub fn in_field(self, name: &'static str) -> Self {
+        self.prepend(PathSegment::Field(name))
+    }
+
+    /// Record that the failures happened at position `index` of a sequence.
+    pub fn at_index(self, index: usize) -> Self {
+        self.prepend(PathSegment::Index(index))
+    }
+
+    /// Record that the failures happened at `key` of a map.
+    pub fn at_key(self, key: impl fmt::Debug) -> Self {
+        self.prepend(PathSegment::Key(format!("{key:?}")))
+    }
+
+    fn prepend(mut self, segment: PathSegment) -> Self {
+        for failure in &mut self.failures {
+            failure.path.insert(0, segment.clone());
+        }
+        self
+    }
+}
+
+impl<E> From<E> for MirrorError
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl fmt::Display for MirrorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, failure) in self.failures.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{failure}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Failure {
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    pub fn error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.error
+    }
+
+    /// The path in the usual notation, `orders[3].items[0].price`.
+    pub fn location(&self) -> String {
+        let mut location = String::new();
+        for segment in &self.path {
+            match segment {
+                PathSegment::Field(name) if location.is_empty() => location.push_str(name),
+                PathSegment::Field(name) => {
+                    location.push('.');
+                    location.push_str(name);
+                }
+                PathSegment::Index(index) => location.push_str(&format!("[{index}]")),
+                PathSegment::Key(key) => location.push_str(&format!("[{key}]")),
+            }
+        }
+        location
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.error)
+        } else {
+            write!(f, "{}: {}", self.location(), self.error)
+        }
+    }
+}
+
+/// Gathers the failures of several values so that all of them are reported at once, used
+/// by [`TryMirror::try_mirror_all`](crate::TryMirror::try_mirror_all) and by
+/// `#[dg(collect_errors)]`.
+#[derive(Debug, Default)]
+pub struct ErrorCollector {
+    failures: Vec<Failure>,
+}
+
+impl ErrorCollector {
+    /// Run one conversion, keeping its failures and returning its value if it succeeded.
+    pub fn collect<T>(&mut self, convert: impl FnOnce() -> Result<T, MirrorError>) -> Option<T> {
+        match convert() {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.failures.extend(error.failures);
+                None
+            }
+        }
+    }
+
+    /// `Err` with every collected failure if there were any.
+    pub fn finish(self) -> Result<(), MirrorError> {
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(self.into_error())
+        }
+    }
+
+    pub fn into_error(self) -> MirrorError {
+        MirrorError {
+            failures: self.failures,
+        }
+    }
+}
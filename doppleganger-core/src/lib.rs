@@ -1,4 +1,8 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, convert::Infallible, marker::PhantomData};
+
+mod error;
+
+pub use error::{ErrorCollector, Failure, MirrorError, PathSegment};
 
 pub trait Mirror {
     type Source;
@@ -70,6 +74,401 @@ where
     }
 }
 
+/// Mirrors `source` with the [`Mirror`] impl of its own type, for when the type of the
+/// destination is not spelled out, such as the fields of a `#[dg(flatten(...))]` struct.
+#[inline]
+pub fn mirror_as_source<T>(source: T) -> T::Dest
+where
+    T: Mirror<Source = T>,
+{
+    T::mirror(source)
+}
+
+/// Mirrors `source` with the [`Mirror`] impl of the type it is mirrored into, the reverse
+/// of [`mirror_as_source`].
+#[inline]
+pub fn mirror_as_dest<T>(source: T::Source) -> T
+where
+    T: Mirror<Dest = T>,
+{
+    T::mirror(source)
+}
+
+/// A [`Mirror`] into an existing destination, overwriting only the fields the conversion
+/// maps.
+///
+/// Fields of the destination the source does not provide, such as ignored or defaulted
+/// ones, keep their current value. [`MirrorIntoFrom`] is the same update for a
+/// [`MirrorFrom`] conversion.
+pub trait MirrorInto: Mirror {
+    fn mirror_into(source: Self::Source, dest: &mut Self::Dest);
+}
+
+/// A conversion into `Self` from one specific source type.
+///
+/// Unlike [`Mirror`] a type can implement this once per source, which lets a single type
+/// derive several `#[dg(forward = ...)]` or `#[dg(backward = ...)]` targets.
+pub trait MirrorFrom<S>: Sized {
+    fn mirror_from(source: S) -> Self;
+}
+
+/// The reciprocal of [`MirrorFrom`], implemented for every source of a [`MirrorFrom`].
+pub trait MirrorTo<D> {
+    fn mirror_to(self) -> D;
+}
+
+impl<S, D> MirrorTo<D> for S
+where
+    D: MirrorFrom<S>,
+{
+    #[inline]
+    fn mirror_to(self) -> D {
+        D::mirror_from(self)
+    }
+}
+
+/// A [`MirrorFrom`] into an existing destination, overwriting only the fields the
+/// conversion maps, like [`MirrorInto`] does for a [`Mirror`].
+///
+/// This is the in place update of a type with several targets, or of the backward half of
+/// a `#[dg(both = ...)]`, e.g. `UserEntity::mirror_into_from(dto, &mut entity)`.
+pub trait MirrorIntoFrom<S>: MirrorFrom<S> {
+    fn mirror_into_from(source: S, dest: &mut Self);
+}
+
+impl<S, D> MirrorFrom<Vec<S>> for Vec<D>
+where
+    D: MirrorFrom<S>,
+{
+    #[inline]
+    fn mirror_from(source: Vec<S>) -> Self {
+        source.into_iter().map(D::mirror_from).collect()
+    }
+}
+
+impl<S, D> MirrorFrom<Option<S>> for Option<D>
+where
+    D: MirrorFrom<S>,
+{
+    #[inline]
+    fn mirror_from(source: Option<S>) -> Self {
+        source.map(D::mirror_from)
+    }
+}
+
+impl<S, D, SE, DE> MirrorFrom<Result<S, SE>> for Result<D, DE>
+where
+    D: MirrorFrom<S>,
+    DE: MirrorFrom<SE>,
+{
+    #[inline]
+    fn mirror_from(source: Result<S, SE>) -> Self {
+        source.map(D::mirror_from).map_err(DE::mirror_from)
+    }
+}
+
+impl<SK, SV, DK, DV> MirrorFrom<HashMap<SK, SV>> for HashMap<DK, DV>
+where
+    DK: MirrorFrom<SK> + Eq + std::hash::Hash,
+    DV: MirrorFrom<SV>,
+{
+    fn mirror_from(source: HashMap<SK, SV>) -> Self {
+        source
+            .into_iter()
+            .map(|(k, v)| (DK::mirror_from(k), DV::mirror_from(v)))
+            .collect()
+    }
+}
+
+/// A [`Mirror`] from a borrowed source, for when the source has to be kept around.
+///
+/// Primitives are cloned, containers convert their elements from references, so only the
+/// parts that end up in the destination are copied.
+pub trait MirrorRef {
+    type Source;
+    type Dest;
+
+    fn mirror_ref(source: &Self::Source) -> Self::Dest;
+}
+
+impl<T> MirrorRef for Vec<T>
+where
+    T: MirrorRef,
+{
+    type Source = Vec<T::Source>;
+
+    type Dest = Vec<T::Dest>;
+
+    #[inline]
+    fn mirror_ref(source: &Self::Source) -> Self::Dest {
+        source.iter().map(<T as MirrorRef>::mirror_ref).collect()
+    }
+}
+
+impl<T> MirrorRef for Option<T>
+where
+    T: MirrorRef,
+{
+    type Source = Option<T::Source>;
+
+    type Dest = Option<T::Dest>;
+
+    #[inline]
+    fn mirror_ref(source: &Self::Source) -> Self::Dest {
+        source.as_ref().map(<T as MirrorRef>::mirror_ref)
+    }
+}
+
+impl<T, E> MirrorRef for Result<T, E>
+where
+    T: MirrorRef,
+    E: MirrorRef,
+{
+    type Source = Result<T::Source, E::Source>;
+
+    type Dest = Result<T::Dest, E::Dest>;
+
+    #[inline]
+    fn mirror_ref(source: &Self::Source) -> Self::Dest {
+        source
+            .as_ref()
+            .map(<T as MirrorRef>::mirror_ref)
+            .map_err(<E as MirrorRef>::mirror_ref)
+    }
+}
+
+impl<K, V> MirrorRef for HashMap<K, V>
+where
+    K: MirrorRef,
+    V: MirrorRef,
+    K::Dest: Eq + std::hash::Hash,
+{
+    type Source = HashMap<K::Source, V::Source>;
+
+    type Dest = HashMap<K::Dest, V::Dest>;
+
+    fn mirror_ref(source: &Self::Source) -> Self::Dest {
+        source
+            .iter()
+            .map(|(k, v)| {
+                (
+                    <K as MirrorRef>::mirror_ref(k),
+                    <V as MirrorRef>::mirror_ref(v),
+                )
+            })
+            .collect()
+    }
+}
+
+/// A fallible [`Mirror`], for conversions that can reject their source such as parsing
+/// wire strings into domain newtypes or narrowing integers.
+///
+/// Primitives and derived infallible types implement it with [`Infallible`] as the error.
+/// There is no blanket impl for every [`Mirror`]: it would overlap with the container and
+/// derived impls, since `Vec<T>` is both `Mirror` and `TryMirror` whenever `T` is, and
+/// coherence rejects the pair. Hand written `Mirror` types are used through [`AsTryMirror`]
+/// instead, or as a derived field with `#[dg(as_try_mirror)]`. The containers stop at
+/// the first rejected element and report a [`MirrorError`] with its index or key,
+/// [`try_mirror_all`](TryMirror::try_mirror_all) reports every rejected element instead.
+pub trait TryMirror {
+    type Source;
+    type Dest;
+    type Error;
+
+    fn try_mirror(source: Self::Source) -> Result<Self::Dest, Self::Error>;
+
+    /// Like [`try_mirror`](TryMirror::try_mirror), but containers convert every element and
+    /// report the failure of each rejected one. `#[dg(collect_errors)]` converts its fields
+    /// with it.
+    fn try_mirror_all(source: Self::Source) -> Result<Self::Dest, MirrorError>
+    where
+        Self::Error: Into<MirrorError>,
+    {
+        Self::try_mirror(source).map_err(Into::into)
+    }
+}
+
+impl<T> TryMirror for Vec<T>
+where
+    T: TryMirror,
+    T::Error: Into<MirrorError>,
+{
+    type Source = Vec<T::Source>;
+
+    type Dest = Vec<T::Dest>;
+
+    type Error = MirrorError;
+
+    fn try_mirror(source: Self::Source) -> Result<Self::Dest, Self::Error> {
+        source
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| {
+                <T as TryMirror>::try_mirror(item).map_err(|err| err.into().at_index(i))
+            })
+            .collect()
+    }
+
+    fn try_mirror_all(source: Self::Source) -> Result<Self::Dest, MirrorError> {
+        let mut errors = ErrorCollector::default();
+        let items = source
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, item)| {
+                errors.collect(|| {
+                    <T as TryMirror>::try_mirror_all(item).map_err(|err| err.at_index(i))
+                })
+            })
+            .collect();
+        errors.finish()?;
+        Ok(items)
+    }
+}
+
+impl<T> TryMirror for Option<T>
+where
+    T: TryMirror,
+    T::Error: Into<MirrorError>,
+{
+    type Source = Option<T::Source>;
+
+    type Dest = Option<T::Dest>;
+
+    type Error = MirrorError;
+
+    #[inline]
+    fn try_mirror(source: Self::Source) -> Result<Self::Dest, Self::Error> {
+        source
+            .map(<T as TryMirror>::try_mirror)
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    #[inline]
+    fn try_mirror_all(source: Self::Source) -> Result<Self::Dest, MirrorError> {
+        source.map(<T as TryMirror>::try_mirror_all).transpose()
+    }
+}
+
+impl<T, E> TryMirror for Result<T, E>
+where
+    T: TryMirror,
+    E: TryMirror,
+    T::Error: Into<MirrorError>,
+    E::Error: Into<MirrorError>,
+{
+    type Source = Result<T::Source, E::Source>;
+
+    type Dest = Result<T::Dest, E::Dest>;
+
+    type Error = MirrorError;
+
+    #[inline]
+    fn try_mirror(source: Self::Source) -> Result<Self::Dest, Self::Error> {
+        Ok(match source {
+            Ok(value) => Ok(<T as TryMirror>::try_mirror(value).map_err(Into::into)?),
+            Err(err) => Err(<E as TryMirror>::try_mirror(err).map_err(Into::into)?),
+        })
+    }
+
+    #[inline]
+    fn try_mirror_all(source: Self::Source) -> Result<Self::Dest, MirrorError> {
+        Ok(match source {
+            Ok(value) => Ok(<T as TryMirror>::try_mirror_all(value)?),
+            Err(err) => Err(<E as TryMirror>::try_mirror_all(err)?),
+        })
+    }
+}
+
+impl<K, V> TryMirror for HashMap<K, V>
+where
+    K: TryMirror,
+    V: TryMirror,
+    K::Source: std::fmt::Debug,
+    K::Dest: Eq + std::hash::Hash,
+    K::Error: Into<MirrorError>,
+    V::Error: Into<MirrorError>,
+{
+    type Source = HashMap<K::Source, V::Source>;
+
+    type Dest = HashMap<K::Dest, V::Dest>;
+
+    type Error = MirrorError;
+
+    /// A rejected value records its key in the path, a rejected key is reported as is since
+    /// it was consumed by the conversion.
+    fn try_mirror(source: Self::Source) -> Result<Self::Dest, Self::Error> {
+        source
+            .into_iter()
+            .map(|(k, v)| {
+                let v = <V as TryMirror>::try_mirror(v).map_err(|err| err.into().at_key(&k))?;
+                let k = <K as TryMirror>::try_mirror(k).map_err(Into::into)?;
+                Ok((k, v))
+            })
+            .collect()
+    }
+
+    fn try_mirror_all(source: Self::Source) -> Result<Self::Dest, MirrorError> {
+        let mut errors = ErrorCollector::default();
+        let entries = source
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let v = errors
+                    .collect(|| <V as TryMirror>::try_mirror_all(v).map_err(|err| err.at_key(&k)));
+                let k = errors.collect(|| <K as TryMirror>::try_mirror_all(k));
+                Some((k?, v?))
+            })
+            .collect();
+        errors.finish()?;
+        Ok(entries)
+    }
+}
+
+/// Bridges any [`Mirror`] type to [`TryMirror`] with an [`Infallible`] error.
+///
+/// See [`TryMirror`] for why this is not a blanket impl, e.g.
+/// `AsTryMirror::<Money>::try_mirror(cents)`, or `#[dg(as_try_mirror)]` on a field of a
+/// derived fallible mirror.
+pub struct AsTryMirror<T>(PhantomData<T>);
+
+impl<T> TryMirror for AsTryMirror<T>
+where
+    T: Mirror,
+{
+    type Source = T::Source;
+
+    type Dest = T::Dest;
+
+    type Error = Infallible;
+
+    #[inline]
+    fn try_mirror(source: Self::Source) -> Result<Self::Dest, Self::Error> {
+        Ok(T::mirror(source))
+    }
+}
+
+/// Builds the parent of a `#[dg(to = "parent.field")]` destination field.
+///
+/// The parent starts from its [`Default`] and every field that targets it is then assigned,
+/// the fields no `to` targets keep their default.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` must implement `Default` to be the parent of a #[dg(to = \"...\")] field",
+    label = "the fields no #[dg(to = \"...\")] targets are filled from `Default`"
+)]
+pub trait NestedDefault {
+    fn nested_default() -> Self;
+}
+
+impl<T> NestedDefault for T
+where
+    T: Default,
+{
+    #[inline]
+    fn nested_default() -> Self {
+        T::default()
+    }
+}
+
 pub trait Primitive: Sized {}
 
 impl<T> Mirror for T
@@ -86,6 +485,66 @@ where
     }
 }
 
+impl<T> TryMirror for T
+where
+    T: Primitive,
+{
+    type Source = Self;
+
+    type Dest = Self;
+
+    type Error = Infallible;
+
+    #[inline]
+    fn try_mirror(source: Self::Source) -> Result<Self::Dest, Self::Error> {
+        Ok(source)
+    }
+}
+
+impl<T> MirrorInto for T
+where
+    T: Primitive,
+{
+    #[inline]
+    fn mirror_into(source: Self::Source, dest: &mut Self::Dest) {
+        *dest = source;
+    }
+}
+
+impl<T> MirrorIntoFrom<T> for T
+where
+    T: Primitive,
+{
+    #[inline]
+    fn mirror_into_from(source: T, dest: &mut Self) {
+        *dest = source;
+    }
+}
+
+impl<T> MirrorRef for T
+where
+    T: Primitive + Clone,
+{
+    type Source = Self;
+
+    type Dest = Self;
+
+    #[inline]
+    fn mirror_ref(source: &Self::Source) -> Self::Dest {
+        source.clone()
+    }
+}
+
+impl<T> MirrorFrom<T> for T
+where
+    T: Primitive,
+{
+    #[inline]
+    fn mirror_from(source: T) -> Self {
+        source
+    }
+}
+
 impl Primitive for String {}
 impl Primitive for &str {}
 impl<'a> Primitive for Cow<'a, str> {}
@@ -0,0 +1,736 @@
+use unsynn::*;
+
+keyword! {
+    /// The "pub" keyword.
+    pub KPub = "pub";
+    /// The "struct" keyword.
+    pub KStruct = "struct";
+    /// The "enum" keyword.
+    pub KEnum = "enum";
+    /// The "doc" keyword.
+    pub KDoc = "doc";
+    /// The "repr" keyword.
+    pub KRepr = "repr";
+    /// The "crate" keyword.
+    pub KCrate = "crate";
+    /// The "in" keyword.
+    pub KIn = "in";
+    /// The "const" keyword.
+    pub KConst = "const";
+    /// The "where" keyword.
+    pub KWhere = "where";
+    /// The "mut" keyword.
+    pub KMut = "mut";
+    pub KDg = "dg";
+    /// The "default" keyword.
+    pub KDefault = "default";
+    /// The "rename" keyword.
+    pub KRename = "rename";
+    /// The "flatten" keyword
+    pub KFlatten = "flatten";
+    /// The "forward" keyword
+    pub KForward = "forward";
+    /// The "backward" keyword
+    pub KBackward = "backward";
+    /// the "ignore" keywoard
+    pub KIgnore = "ignore";
+    /// the "map" keyword
+    pub KMap = "map";
+    /// the "index" keyword
+    pub KIndex = "index";
+    /// the "for" keyword
+    pub KFor = "for";
+    /// the "both" keyword
+    pub KBoth = "both";
+    /// the "map_back" keyword
+    pub KMapBack = "map_back";
+    /// the "bound" keyword
+    pub KBound = "bound";
+    /// the "map_then_mirror" keyword
+    pub KMapThenMirror = "map_then_mirror";
+    /// the "mirror_from" keyword
+    pub KMirrorFrom = "mirror_from";
+    /// the "with" keyword
+    pub KWith = "with";
+    /// the "try_forward" keyword
+    pub KTryForward = "try_forward";
+    /// the "try_backward" keyword
+    pub KTryBackward = "try_backward";
+    /// the "try_map" keyword
+    pub KTryMap = "try_map";
+    /// the "as_try_mirror" keyword
+    pub KAsTryMirror = "as_try_mirror";
+    /// the "error" keyword
+    pub KError = "error";
+    /// the "collect_errors" keyword
+    pub KCollectErrors = "collect_errors";
+    /// the "ref" keyword
+    pub KRef = "ref";
+    /// the "generate" keyword
+    pub KGenerate = "generate";
+    /// the "generate_derive" keyword
+    pub KGenerateDerive = "generate_derive";
+    /// the "patch" keyword
+    pub KPatch = "patch";
+    /// the "patch_derive" keyword
+    pub KPatchDerive = "patch_derive";
+    /// the "skip_forward" keyword
+    pub KSkipForward = "skip_forward";
+    /// the "skip_backward" keyword
+    pub KSkipBackward = "skip_backward";
+    /// the "compute" keyword
+    pub KCompute = "compute";
+    /// the "extra" keyword
+    pub KExtra = "extra";
+    /// the "from" keyword
+    pub KFrom = "from";
+    /// the "to" keyword
+    pub KTo = "to";
+}
+
+operator! {
+    /// Represents the '=' operator.
+    pub Eq = "=";
+    /// Represents the ';' operator.
+    pub Semi = ";";
+    /// Represents the apostrophe '\'' operator.
+    pub Apostrophe = "'";
+    /// Represents the double semicolon '::' operator.
+    pub DoubleSemicolon = "::";
+}
+
+/// Parses tokens and groups until `C` is found on the current token tree level.
+pub type VerbatimUntil<C> = Many<Cons<Except<C>, AngleTokenTree>>;
+
+/// Parses tokens and groups until a single `:` is found on the current token tree level.
+/// Path separators `::` are consumed as part of the verbatim tokens.
+pub type VerbatimUntilColon = Many<Either<PathSep, Cons<Except<Colon>, AngleTokenTree>>>;
+
+/// Represents a module path, consisting of an optional path separator followed by
+/// a path-separator-delimited sequence of identifiers.
+pub type ModPath = Cons<Option<PathSep>, DelimitedVec<Ident, PathSep>>;
+
+/// Represents the other type of a mirror, such as `ApiUser` or `api::Paged<T::Dest>`,
+/// consisting of the tokens until a comma on the current token tree level.
+pub type DgType = VerbatimUntil<Comma>;
+
+/// Represents type bounds, consisting of a colon followed by tokens until
+/// a comma, equals sign, or closing angle bracket is encountered.
+pub type Bounds = Cons<Colon, VerbatimUntil<Either<Comma, Eq, Gt>>>;
+
+unsynn! {
+    /// Parses either a `TokenTree` or `<...>` grouping (which is not a [`Group`] as far as proc-macros
+    /// are concerned).
+    #[derive(Clone)]
+    pub struct AngleTokenTree(
+        #[allow(clippy::type_complexity)] // look,
+        pub Either<Cons<Lt, Vec<Cons<Except<Gt>, AngleTokenTree>>, Gt>, TokenTree>,
+    );
+
+    /// Represents an algebraic data type (ADT) declaration, which can be either a struct or enum.
+    pub enum AdtDecl {
+        /// A struct ADT variant.
+        Struct(Struct),
+        /// An enum ADT variant.
+        Enum(Enum),
+    }
+
+    /// Represents visibility modifiers for items.
+    pub enum Vis {
+        /// `pub(in? crate::foo::bar)`/`pub(in? ::foo::bar)`
+        PubIn(Cons<KPub, ParenthesisGroupContaining<Cons<Option<KIn>, ModPath>>>),
+        /// Public visibility, indicated by the "pub" keyword.
+        Pub(KPub),
+    }
+
+    /// Represents an attribute annotation on a field, typically in the form `#[attr]`.
+    pub struct Attribute {
+        /// The pound sign preceding the attribute.
+        pub _pound: Pound,
+        /// The content of the attribute enclosed in square brackets.
+        pub body: BracketGroupContaining<AttributeInner>,
+    }
+
+    /// Represents the inner content of an attribute annotation.
+    pub enum AttributeInner {
+        /// A dopplegagner attribute that can contain specialized metadata.
+        Dg(DopplegangerAttr),
+        /// A documentation attribute typically used for generating documentation.
+        Doc(DocInner),
+        /// A representation attribute that specifies how data should be laid out.
+        Repr(ReprInner),
+        /// Any other attribute represented as a sequence of token trees.
+        Any(Vec<TokenTree>),
+    }
+
+    /// Represents a dg attribute that can contain specialized metadata.
+    pub struct DopplegangerAttr {
+        /// The keyword for the dg attribute.
+        pub _dg: KDg,
+        /// The inner content of the dg attribute.
+        pub inner: ParenthesisGroupContaining<CommaDelimitedVec<DgInner>>,
+    }
+
+    /// Represents the inner content of a dg attribute.
+    pub enum DgInner {
+        /// The direction of the source/dest relation
+        Direction(DgDirection),
+        /// A default attribute with an explicit value (#[dg(default = expr)])
+        DefaultEquals(DefaultEqualsInner),
+        /// A default attribute with no explicit value (#[dg(default)])
+        Default(KDefault),
+        /// A rename attribute that specifies a custom name for a field/variant (#[dg(rename = "custom_name")])
+        Rename(RenameInner),
+        /// Read the field from a nested field of the source (#[dg(from = "address.city")])
+        From(FromInner),
+        /// Write the field into a nested field of the destination (#[dg(to = "meta.created_at")])
+        To(ToInner),
+        /// An index attribute that maps a named field to a tuple position (#[dg(index = 0)])
+        Index(IndexInner),
+        /// Replace the inferred bounds of the generated impls (#[dg(bound = "T: Clone")])
+        Bound(BoundInner),
+        /// Spread the fields of a nested struct into the flat other type (#[dg(flatten(a, b))]).
+        /// The fields are listed because a derive cannot see the nested struct, a bare
+        /// `#[dg(flatten)]` is rejected.
+        Flatten(FlattenInner),
+        /// Ignore the field, optionally only for one target (#[dg(ignore(for = Target))])
+        Ignore(IgnoreInner),
+        /// Ignore the field when the derived type is the source (#[dg(skip_forward)])
+        SkipForward(KSkipForward),
+        /// Ignore the field when the derived type is the destination (#[dg(skip_backward)])
+        SkipBackward(KSkipBackward),
+        /// map the field from one value to another
+        Map(DgMap),
+        /// map the field back when mirroring in both directions
+        MapBack(DgMapBack),
+        /// mirror the output of the map instead of using it as is (#[dg(map_then_mirror)])
+        MapThenMirror(KMapThenMirror),
+        /// convert the field through `MirrorFrom`, for a type with several targets (#[dg(mirror_from)])
+        MirrorFrom(KMirrorFrom),
+        /// convert the field with the `forward`/`backward` fns of a module (#[dg(with = conv::cents)])
+        With(DgWith),
+        /// map the field with a fallible expression in a fallible mirror (#[dg(try_map = parse)])
+        TryMap(DgTryMap),
+        /// convert the field of a fallible mirror with its `Mirror` impl, through `AsTryMirror`
+        /// (#[dg(as_try_mirror)])
+        AsTryMirror(KAsTryMirror),
+        /// The error type of a fallible mirror (#[dg(error = MyError)])
+        Error(ErrorInner),
+        /// Report the errors of every field and of every element of their containers instead
+        /// of the first one (#[dg(collect_errors)])
+        CollectErrors(KCollectErrors),
+        /// Also mirror from a reference to the source (#[dg(ref)])
+        Ref(KRef),
+        /// The derives of a generated type (#[dg(generate_derive(Debug, Clone))])
+        GenerateDerive(GenerateDeriveInner),
+        /// Declare a patch type of optional fields applied to the type (#[dg(patch = UserPatch)])
+        Patch(PatchInner),
+        /// The derives of a patch type (#[dg(patch_derive(Debug, Default))])
+        PatchDerive(PatchDeriveInner),
+        /// compute the field from the whole source (#[dg(compute = full_name)])
+        Compute(DgCompute),
+        /// destination fields computed from the whole source (#[dg(extra(full_name = f))])
+        Extra(ExtraInner),
+        /// Any other item, kept so that unknown or malformed keys can be reported with a
+        /// helpful error instead of failing to parse. Must stay the last variant.
+        Unknown(UnknownInner)
+    }
+
+    /// An unknown or malformed item in a dg attribute, e.g. `renmae = "x"`
+    pub struct UnknownInner {
+        /// The key of the item.
+        pub key: Ident,
+        /// Whatever follows the key, as verbatim until comma.
+        pub rest: Option<VerbatimUntil<Comma>>,
+    }
+
+    /// Inner value for #[dg(map = ...)], any expression that can be called with the field,
+    /// e.g. `Arc::from`, `|s: String| s.trim().to_owned()` or `Into::<Arc<str>>::into`
+    pub struct DgMap {
+        _map: KMap,
+        _eq: Eq,
+        /// The mapping expression, as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>
+    }
+
+    /// Inner value for #[dg(map_back = ...)], any expression like in #[dg(map = ...)]
+    pub struct DgMapBack {
+        _map_back: KMapBack,
+        _eq: Eq,
+        /// The mapping expression, as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>
+    }
+
+    /// Inner value for #[dg(compute = ...)], an expression called with a reference to the
+    /// whole source
+    pub struct DgCompute {
+        _compute: KCompute,
+        _eq: Eq,
+        /// The computing expression, as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>
+    }
+
+    /// Inner value for #[dg(extra(...))]
+    pub struct ExtraInner {
+        /// The "extra" keyword.
+        pub _kw_extra: KExtra,
+        /// The computed destination fields.
+        pub fields: ParenthesisGroupContaining<CommaDelimitedVec<ExtraField>>,
+    }
+
+    /// One `field = expr` of #[dg(extra(...))]
+    pub struct ExtraField {
+        /// The destination field.
+        pub name: Ident,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The computing expression, as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[dg(try_map = ...)], an expression returning a `Result` whose error
+    /// converts into the error of the mirror
+    pub struct DgTryMap {
+        _try_map: KTryMap,
+        _eq: Eq,
+        /// The mapping expression, as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>
+    }
+
+    /// Inner value for #[dg(error = ...)]
+    pub struct ErrorInner {
+        /// The "error" keyword.
+        pub _kw_error: KError,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The error type.
+        pub path: DgType,
+    }
+
+    /// Inner value for #[dg(with = ...)], a module exposing `forward` and `backward` fns
+    pub struct DgWith {
+        /// The "with" keyword.
+        pub _kw_with: KWith,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The path of the module.
+        pub path: ModPath,
+    }
+
+    pub enum DgDirection {
+        Forward {
+            _fw: KForward,
+            _eq: Eq,
+            path: DgType
+        },
+        Backward {
+            _bw: KBackward,
+            _eq: Eq,
+            path: DgType
+        },
+        Both {
+            _both: KBoth,
+            _eq: Eq,
+            path: DgType
+        },
+        TryForward {
+            _try_fw: KTryForward,
+            _eq: Eq,
+            path: DgType
+        },
+        TryBackward {
+            _try_bw: KTryBackward,
+            _eq: Eq,
+            path: DgType
+        },
+        /// A forward target declared by the derive itself
+        Generate {
+            _generate: KGenerate,
+            _eq: Eq,
+            path: DgType
+        }
+    }
+
+    /// Restricts a field attribute to a single target, e.g. the `for = ApiUser` in
+    /// `#[dg(ignore(for = ApiUser))]`
+    pub struct DgFor {
+        /// The "for" keyword.
+        pub _kw_for: KFor,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The target the attribute applies to.
+        pub path: DgType,
+    }
+
+    /// Inner value for #[dg(ignore)] and #[dg(ignore(for = ...))]
+    pub struct IgnoreInner {
+        /// The "ignore" keyword.
+        pub _kw_ignore: KIgnore,
+        /// The optional target restriction.
+        pub target: Option<ParenthesisGroupContaining<DgFor>>,
+    }
+
+    /// Inner value for #[dg(flatten(field, ...))]
+    pub struct FlattenInner {
+        /// The "flatten" keyword.
+        pub _kw_flatten: KFlatten,
+        /// The fields of the nested struct that are spread into the flat struct, all of them
+        /// when the nested struct is rebuilt from the flat one.
+        pub fields: ParenthesisGroupContaining<CommaDelimitedVec<Ident>>,
+    }
+
+
+    /// Inner value for #[dg(generate_derive(...))]
+    pub struct GenerateDeriveInner {
+        /// The "generate_derive" keyword.
+        pub _kw_generate_derive: KGenerateDerive,
+        /// The derive macros, e.g. `Debug` or `serde::Serialize`.
+        pub derives: ParenthesisGroupContaining<CommaDelimitedVec<ModPath>>,
+    }
+
+    /// Inner value for #[dg(patch = ...)]
+    pub struct PatchInner {
+        /// The "patch" keyword.
+        pub _kw_patch: KPatch,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The name of the patch type.
+        pub path: DgType,
+    }
+
+    /// Inner value for #[dg(patch_derive(...))]
+    pub struct PatchDeriveInner {
+        /// The "patch_derive" keyword.
+        pub _kw_patch_derive: KPatchDerive,
+        /// The derive macros, e.g. `Default` or `serde::Deserialize`.
+        pub derives: ParenthesisGroupContaining<CommaDelimitedVec<ModPath>>,
+    }
+
+    /// Inner value for #[dg(default = ...)]
+    pub struct DefaultEqualsInner {
+        /// The "default" keyword.
+        pub _kw_default: KDefault,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The value assigned, as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[dg(rename = ...)] and #[dg(rename(for = ...) = ...)]
+    pub struct RenameInner {
+        /// The "rename" keyword.
+        pub _kw_rename: KRename,
+        /// The optional target restriction.
+        pub target: Option<ParenthesisGroupContaining<DgFor>>,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The value assigned, as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Inner value for #[dg(from = ...)]
+    pub struct FromInner {
+        /// The "from" keyword.
+        pub _kw_from: KFrom,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The dotted path of the field on the source, as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Inner value for #[dg(to = ...)], the parent of a nested path is built from its
+    /// `Default` and then filled by every field targeting it
+    pub struct ToInner {
+        /// The "to" keyword.
+        pub _kw_to: KTo,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The dotted path of the field on the destination, as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Inner value for #[dg(bound = ...)]
+    pub struct BoundInner {
+        /// The "bound" keyword.
+        pub _kw_bound: KBound,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The where clause predicates, as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Inner value for #[dg(index = ...)]
+    pub struct IndexInner {
+        /// The "index" keyword.
+        pub _kw_index: KIndex,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The position in the tuple struct, as an integer literal.
+        pub value: LiteralInteger,
+    }
+
+    /// Represents documentation for an item.
+    pub struct DocInner {
+        /// The "doc" keyword.
+        pub _kw_doc: KDoc,
+        /// The equality operator.
+        pub _eq: Eq,
+        /// The documentation content as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Represents the inner content of a `repr` attribute, typically used for specifying
+    /// memory layout or representation hints.
+    pub struct ReprInner {
+        /// The "repr" keyword.
+        pub _kw_repr: KRepr,
+        /// The representation attributes enclosed in parentheses.
+        pub attr: ParenthesisGroupContaining<CommaDelimitedVec<Ident>>,
+    }
+
+    /// Represents a struct definition.
+    pub struct Struct {
+        /// Attributes applied to the struct.
+        pub attributes: Vec<Attribute>,
+        /// The visibility modifier of the struct (e.g., `pub`).
+        pub _vis: Option<Vis>,
+        /// The "struct" keyword.
+        pub _kw_struct: KStruct,
+        /// The name of the struct.
+        pub name: Ident,
+        /// Generic parameters for the struct, if any.
+        pub generics: Option<GenericParams>,
+        /// The variant of struct (unit, tuple, or regular struct with named fields).
+        pub kind: StructKind,
+    }
+
+    /// Represents the generic parameters of a struct or enum definition, enclosed in angle brackets.
+    /// e.g., `<'a, T: Trait, const N: usize>`.
+    pub struct GenericParams {
+        /// The opening angle bracket `<`.
+        pub _lt: Lt,
+        /// The comma-delimited list of generic parameters.
+        pub params: CommaDelimitedVec<GenericParam>,
+        /// The closing angle bracket `>`.
+        pub _gt: Gt,
+    }
+
+    /// Represents a single generic parameter within a `GenericParams` list.
+    pub enum GenericParam {
+        /// A lifetime parameter, e.g., `'a` or `'a: 'b + 'c`.
+        Lifetime {
+            /// The lifetime identifier (e.g., `'a`).
+            name: Lifetime,
+            /// Optional lifetime bounds (e.g., `: 'b + 'c`).
+            bounds: Option<Cons<Colon, VerbatimUntil<Either<Comma, Gt>>>>,
+        },
+        /// A const generic parameter, e.g., `const N: usize = 10`.
+        Const {
+            /// The `const` keyword.
+            _const: KConst,
+            /// The name of the const parameter (e.g., `N`).
+            name: Ident,
+            /// The colon separating the name and type.
+            _colon: Colon,
+            /// The type of the const parameter (e.g., `usize`).
+            typ: VerbatimUntil<Either<Comma, Gt, Eq>>,
+            /// An optional default value (e.g., `= 10`).
+            default: Option<Cons<Eq, VerbatimUntil<Either<Comma, Gt>>>>,
+        },
+        /// A type parameter, e.g., `T: Trait = DefaultType`.
+        Type {
+            /// The name of the type parameter (e.g., `T`).
+            name: Ident,
+            /// Optional type bounds (e.g., `: Trait`).
+            bounds: Option<Bounds>,
+            /// An optional default type (e.g., `= DefaultType`).
+            default: Option<Cons<Eq, VerbatimUntil<Either<Comma, Gt>>>>,
+        },
+    }
+
+    /// Represents a `where` clause attached to a definition.
+    /// e.g., `where T: Trait, 'a: 'b`.
+    #[derive(Clone)]
+    pub struct WhereClauses {
+        /// The `where` keyword.
+        pub _kw_where: KWhere,
+        /// The comma-delimited list of where clause predicates.
+        pub clauses: CommaDelimitedVec<WhereClause>,
+    }
+
+    /// Represents a single predicate within a `where` clause.
+    /// e.g., `T: Trait` or `'a: 'b`.
+    #[derive(Clone)]
+    pub struct WhereClause {
+        /// The type or lifetime being constrained (e.g., `T`, `'a`, `::std::vec::Vec<T>` or
+        /// `for<'a> &'a T`).
+        pub _pred: VerbatimUntilColon,
+        /// The colon separating the constrained item and its bounds.
+        pub _colon: Colon,
+        /// The bounds applied to the type or lifetime (e.g., `Trait` or `'b`).
+        pub bounds: VerbatimUntil<Either<Comma, Semicolon, BraceGroup>>,
+    }
+
+    /// Represents the kind of a struct definition.
+    pub enum StructKind {
+        /// A regular struct with named fields, e.g., `struct Foo { bar: u32 }`.
+        Struct {
+            /// Optional where clauses.
+            clauses: Option<WhereClauses>,
+            /// The fields enclosed in braces `{}`.
+            fields: BraceGroupContaining<CommaDelimitedVec<StructField>>,
+        },
+        /// A tuple struct, e.g., `struct Foo(u32, String);`.
+        TupleStruct {
+            /// The fields enclosed in parentheses `()`.
+            fields: ParenthesisGroupContaining<CommaDelimitedVec<TupleField>>,
+            /// Optional where clauses.
+            clauses: Option<WhereClauses>,
+            /// The trailing semicolon `;`.
+            semi: Semi,
+        },
+        /// A unit struct, e.g., `struct Foo;`.
+        UnitStruct {
+            /// Optional where clauses.
+            clauses: Option<WhereClauses>,
+            /// The trailing semicolon `;`.
+            semi: Semi,
+        },
+    }
+
+    /// Represents a lifetime annotation, like `'a`.
+    pub struct Lifetime {
+        /// The apostrophe `'` starting the lifetime.
+        pub _apostrophe: PunctJoint<'\''>,
+        /// The identifier name of the lifetime (e.g., `a`).
+        pub name: Ident,
+    }
+
+    /// Represents a simple expression, currently only integer literals.
+    /// Used potentially for const generic default values.
+    pub enum Expr {
+        /// An integer literal expression.
+        Integer(LiteralInteger),
+    }
+
+    /// Represents either the `const` or `mut` keyword, often used with pointers.
+    pub enum ConstOrMut {
+        /// The `const` keyword.
+        Const(KConst),
+        /// The `mut` keyword.
+        Mut(KMut),
+    }
+
+    /// Represents a field within a regular struct definition.
+    /// e.g., `pub name: String`.
+    pub struct StructField {
+        /// Attributes applied to the field (e.g., `#[doc = "..."]`).
+        pub attributes: Vec<Attribute>,
+        /// Optional visibility modifier (e.g., `pub`).
+        pub _vis: Option<Vis>,
+        /// The name of the field.
+        pub name: Ident,
+        /// The colon separating the name and type.
+        pub _colon: Colon,
+        /// The type of the field.
+        pub typ: VerbatimUntil<Comma>,
+    }
+
+    /// Represents a field within a tuple struct definition.
+    /// e.g., `pub String`.
+    pub struct TupleField {
+        /// Attributes applied to the field (e.g., `#[doc = "..."]`).
+        pub attributes: Vec<Attribute>,
+        /// Optional visibility modifier (e.g., `pub`).
+        pub vis: Option<Vis>,
+        /// The type of the field.
+        pub typ: VerbatimUntil<Comma>,
+    }
+
+    /// Represents an enum definition.
+    /// e.g., `#[repr(u8)] pub enum MyEnum<T> where T: Clone { Variant1, Variant2(T) }`.
+    pub struct Enum {
+        /// Attributes applied to the enum (e.g., `#[repr(...)]`).
+        pub attributes: Vec<Attribute>,
+        /// Optional visibility modifier (e.g., `pub`, `pub(crate)`, etc.).
+        pub _vis: Option<Vis>,
+        /// The `enum` keyword.
+        pub _kw_enum: KEnum,
+        /// The name of the enum.
+        pub name: Ident,
+        /// Optional generic parameters.
+        pub generics: Option<GenericParams>,
+        /// Optional where clauses.
+        pub clauses: Option<WhereClauses>,
+        /// The enum variants enclosed in braces `{}`.
+        pub body: BraceGroupContaining<CommaDelimitedVec<EnumVariantLike>>,
+    }
+
+    /// Represents a variant of an enum, including the optional discriminant value
+    pub struct EnumVariantLike {
+        /// The actual variant
+        pub variant: EnumVariantData,
+        /// The optional discriminant value
+        pub discriminant: Option<Cons<Eq, VerbatimUntil<Comma>>>
+    }
+
+    /// Represents the different kinds of variants an enum can have.
+    pub enum EnumVariantData {
+        /// A tuple-like variant, e.g., `Variant(u32, String)`.
+        Tuple(TupleVariant),
+        /// A struct-like variant, e.g., `Variant { field1: u32, field2: String }`.
+        Struct(StructEnumVariant),
+        /// A unit-like variant, e.g., `Variant`.
+        Unit(UnitVariant),
+    }
+
+    /// Represents a unit-like enum variant.
+    /// e.g., `MyVariant`.
+    pub struct UnitVariant {
+        /// Attributes applied to the variant.
+        pub attributes: Vec<Attribute>,
+        /// The name of the variant.
+        pub name: Ident,
+    }
+
+    /// Represents a tuple-like enum variant.
+    /// e.g., `MyVariant(u32, String)`.
+    pub struct TupleVariant {
+        /// Attributes applied to the variant.
+        pub attributes: Vec<Attribute>,
+        /// The name of the variant.
+        pub name: Ident,
+        /// The fields enclosed in parentheses `()`.
+        pub fields: ParenthesisGroupContaining<CommaDelimitedVec<TupleField>>,
+    }
+
+    /// Represents a struct-like enum variant.
+    /// e.g., `MyVariant { field1: u32, field2: String }`.
+    pub struct StructEnumVariant {
+        /// Attributes applied to the variant.
+        pub attributes: Vec<Attribute>,
+        /// The name of the variant.
+        pub name: Ident,
+        /// The fields enclosed in braces `{}`.
+        pub fields: BraceGroupContaining<CommaDelimitedVec<StructField>>,
+    }
+
+    /// A lifetime or a tokentree, used to gather lifetimes in type definitions
+    pub enum LifetimeOrTt {
+        /// A lifetime annotation.
+        Lifetime(Lifetime),
+        /// A single token tree.
+        TokenTree(TokenTree),
+    }
+}
+
+impl DgInner {
+    /// The span of the leading keyword of this item, e.g. the `map` in `map = to_cents`,
+    /// used to point diagnostics at the offending part of a `#[dg(...)]` attribute.
+    pub fn span(&self) -> Span {
+        self.to_token_stream()
+            .into_iter()
+            .next()
+            .map_or_else(Span::call_site, |token| token.span())
+    }
+}
@@ -60,3 +60,469 @@ fn it_parses_mod_path() {
         assert_eq!(f, "HeaderMap");
     });
 }
+
+#[test]
+fn it_parses_dg_index() {
+    let input = quote! {
+        #[dg(index = 1)]
+        pub name: String
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Index(index) => {
+            assert_eq!(index.value.value(), 1);
+        });
+    });
+}
+
+#[test]
+fn it_parses_dg_for_target() {
+    let input = quote! {
+        #[dg(ignore(for = AuditUser), rename(for = api::ApiUser) = "username")]
+        pub name: String
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Ignore(IgnoreInner { target: Some(target), .. }) => {
+            assert_eq!(target.content.path.to_token_stream().to_string(), "AuditUser");
+        });
+        assert_matches!(&dg.inner.content[1].value, DgInner::Rename(RenameInner { target: Some(target), value, .. }) => {
+            assert_eq!(target.content.path.to_token_stream().to_string(), "api :: ApiUser");
+            assert_eq!(value.as_str(), "username");
+        });
+    });
+}
+
+#[test]
+fn it_parses_dg_both_with_map_back() {
+    let input = quote! {
+        #[dg(both = DbRow)]
+        pub struct Row {
+            #[dg(map = to_cents, map_back = from_cents)]
+            pub price: f64,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Direction(DgDirection::Both { .. }));
+    });
+    assert_matches!(&parsed.kind, StructKind::Struct { fields, .. } => {
+        assert_matches!(&fields.content[0].value.attributes[0].body.content, AttributeInner::Dg(dg) => {
+            assert_matches!(&dg.inner.content[0].value, DgInner::Map(_));
+            assert_matches!(&dg.inner.content[1].value, DgInner::MapBack(_));
+        });
+    });
+}
+
+#[test]
+fn it_parses_dg_flatten() {
+    let input = quote! {
+        #[dg(flatten(street, city))]
+        pub address: Address
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Flatten(flatten) => {
+            let fields: Vec<_> = flatten.fields.content.iter().map(|f| f.value.to_string()).collect();
+            assert_eq!(fields, ["street", "city"]);
+        });
+    });
+}
+
+#[test]
+fn it_parses_where_clauses_with_absolute_paths_and_hrtb() {
+    let input = quote! {
+        pub struct Wrapper<T, F>
+        where
+            ::std::vec::Vec<T>: ::core::fmt::Debug,
+            <T as ::core::ops::Add>::Output: Clone,
+            for<'a> &'a T: ::core::ops::Add<&'a T>,
+            F: for<'a> Fn(&'a T) -> bool,
+        {
+            pub value: T,
+            pub filter: F,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.kind, StructKind::Struct { clauses: Some(clauses), fields } => {
+        let preds: Vec<_> = clauses
+            .clauses
+            .iter()
+            .map(|c| c.value._pred.to_token_stream().to_string())
+            .collect();
+        assert_eq!(
+            preds,
+            [
+                ":: std :: vec :: Vec < T >",
+                "< T as :: core :: ops :: Add > :: Output",
+                "for < 'a > & 'a T",
+                "F",
+            ]
+        );
+        assert_eq!(fields.content.len(), 2);
+    });
+}
+
+#[test]
+fn it_parses_generic_dg_direction_and_bound() {
+    let input = quote! {
+        #[dg(forward = api::Paged<T::Dest, Vec<U>>, bound = "T: Mirror<Source = T>")]
+        pub struct Paged<T, U = u8> {
+            pub items: Vec<T>,
+            pub extra: Vec<U>,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Direction(DgDirection::Forward { path, .. }) => {
+            assert_eq!(path.to_token_stream().to_string(), "api :: Paged < T :: Dest , Vec < U > >");
+        });
+        assert_matches!(&dg.inner.content[1].value, DgInner::Bound(bound) => {
+            assert_eq!(bound.value.as_str(), "T: Mirror<Source = T>");
+        });
+    });
+}
+
+#[test]
+fn it_keeps_dg_inner_spans() {
+    let mut it = r#"#[dg(rename = "username", map = to_upper)] name: String"#.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        let columns: Vec<_> = dg
+            .inner
+            .content
+            .iter()
+            .map(|inner| inner.value.span().start().column)
+            .collect();
+        assert_eq!(columns, [5, 26]);
+    });
+    assert_eq!(parsed.name.span().start().column, 43);
+}
+
+#[test]
+fn it_keeps_unknown_dg_keys() {
+    let input = quote! {
+        #[dg(renmae = "username", rename = 5, ignore)]
+        pub name: String
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Unknown(unknown) => {
+            assert_eq!(unknown.key.to_string(), "renmae");
+        });
+        assert_matches!(&dg.inner.content[1].value, DgInner::Unknown(unknown) => {
+            assert_eq!(unknown.key.to_string(), "rename");
+        });
+        assert_matches!(&dg.inner.content[2].value, DgInner::Ignore(_));
+    });
+}
+
+#[test]
+fn it_parses_dg_map_then_mirror() {
+    let input = quote! {
+        #[dg(map = normalize, map_then_mirror)]
+        pub items: Vec<T>
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Map(_));
+        assert_matches!(&dg.inner.content[1].value, DgInner::MapThenMirror(_));
+    });
+}
+
+#[test]
+fn it_parses_dg_map_expressions() {
+    let input = quote! {
+        #[dg(map = |s: String| s.trim().to_owned(), map_back = Into::<Arc<str>>::into, rename = "x")]
+        pub name: String
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Map(map) => {
+            assert_eq!(
+                map.expr.to_token_stream().to_string(),
+                "| s : String | s . trim () . to_owned ()"
+            );
+        });
+        assert_matches!(&dg.inner.content[1].value, DgInner::MapBack(map_back) => {
+            assert_eq!(
+                map_back.expr.to_token_stream().to_string(),
+                "Into :: < Arc < str > > :: into"
+            );
+        });
+        assert_matches!(&dg.inner.content[2].value, DgInner::Rename(_));
+    });
+}
+
+#[test]
+fn it_parses_dg_with() {
+    let input = quote! {
+        #[dg(with = crate::conv::cents)]
+        pub price: f64
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::With(with) => {
+            assert_eq!(with.path.to_token_stream().to_string(), "crate :: conv :: cents");
+        });
+    });
+}
+
+#[test]
+fn it_parses_fallible_dg_attributes() {
+    let input = quote! {
+        #[dg(try_backward = WireUser, error = errors::ValidationError)]
+        pub struct User {
+            #[dg(try_map = Email::parse)]
+            pub email: Email,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Direction(DgDirection::TryBackward { path, .. }) => {
+            assert_eq!(path.to_token_stream().to_string(), "WireUser");
+        });
+        assert_matches!(&dg.inner.content[1].value, DgInner::Error(error) => {
+            assert_eq!(error.path.to_token_stream().to_string(), "errors :: ValidationError");
+        });
+    });
+    assert_matches!(&parsed.kind, StructKind::Struct { fields, .. } => {
+        assert_matches!(&fields.content[0].value.attributes[0].body.content, AttributeInner::Dg(dg) => {
+            assert_matches!(&dg.inner.content[0].value, DgInner::TryMap(try_map) => {
+                assert_eq!(try_map.expr.to_token_stream().to_string(), "Email :: parse");
+            });
+        });
+    });
+}
+
+#[test]
+fn it_parses_dg_collect_errors() {
+    let input = quote! {
+        #[dg(try_backward = WireUser, collect_errors)]
+        pub struct User {
+            pub email: Email,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[1].value, DgInner::CollectErrors(_));
+    });
+}
+
+#[test]
+fn it_parses_dg_ref() {
+    let input = quote! {
+        #[dg(forward = ApiUser, ref)]
+        pub struct User {
+            pub name: String,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[1].value, DgInner::Ref(_));
+    });
+}
+
+#[test]
+fn it_parses_dg_generate() {
+    let input = quote! {
+        #[dg(generate = ApiUser, generate_derive(Debug, serde::Serialize))]
+        pub struct User {
+            pub name: String,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Direction(DgDirection::Generate { path, .. }) => {
+            assert_eq!(path.to_token_stream().to_string(), "ApiUser");
+        });
+        assert_matches!(&dg.inner.content[1].value, DgInner::GenerateDerive(generate_derive) => {
+            let derives: Vec<_> = generate_derive
+                .derives
+                .content
+                .iter()
+                .map(|d| d.value.to_token_stream().to_string())
+                .collect();
+            assert_eq!(derives, ["Debug", "serde :: Serialize"]);
+        });
+    });
+}
+
+#[test]
+fn it_parses_dg_patch() {
+    let input = quote! {
+        #[dg(patch = UserPatch, patch_derive(Default))]
+        pub struct User {
+            pub name: String,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::Patch(patch) => {
+            assert_eq!(patch.path.to_token_stream().to_string(), "UserPatch");
+        });
+        assert_matches!(&dg.inner.content[1].value, DgInner::PatchDerive(_));
+    });
+}
+
+#[test]
+fn it_parses_dg_skip_directions() {
+    let input = quote! {
+        #[dg(skip_forward)]
+        #[dg(skip_backward)]
+        pub password_hash: String
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::SkipForward(_));
+    });
+    assert_matches!(&parsed.attributes[1].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::SkipBackward(_));
+    });
+}
+
+#[test]
+fn it_parses_dg_compute_and_extra() {
+    let input = quote! {
+        #[dg(forward = ApiTask, extra(is_overdue = tasks::overdue, label = |t: &Task| t.name.clone()))]
+        pub struct Task {
+            #[dg(compute = full_name)]
+            pub name: String,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[1].value, DgInner::Extra(extra) => {
+            let fields: Vec<_> = extra
+                .fields
+                .content
+                .iter()
+                .map(|f| (f.value.name.to_string(), f.value.expr.to_token_stream().to_string()))
+                .collect();
+            assert_eq!(
+                fields,
+                [
+                    ("is_overdue".to_string(), "tasks :: overdue".to_string()),
+                    ("label".to_string(), "| t : & Task | t . name . clone ()".to_string()),
+                ]
+            );
+        });
+    });
+    assert_matches!(&parsed.kind, StructKind::Struct { fields, .. } => {
+        assert_matches!(&fields.content[0].value.attributes[0].body.content, AttributeInner::Dg(dg) => {
+            assert_matches!(&dg.inner.content[0].value, DgInner::Compute(compute) => {
+                assert_eq!(compute.expr.to_token_stream().to_string(), "full_name");
+            });
+        });
+    });
+}
+
+#[test]
+fn it_parses_dg_from_and_to() {
+    let input = quote! {
+        pub struct Customer {
+            #[dg(from = "address.city")]
+            pub city: String,
+            #[dg(to = "meta.created_at")]
+            pub created_at: u64,
+        }
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+
+    assert_matches!(&parsed.kind, StructKind::Struct { fields, .. } => {
+        assert_matches!(&fields.content[0].value.attributes[0].body.content, AttributeInner::Dg(dg) => {
+            assert_matches!(&dg.inner.content[0].value, DgInner::From(from) => {
+                assert_eq!(from.value.to_token_stream().to_string(), "\"address.city\"");
+            });
+        });
+        assert_matches!(&fields.content[1].value.attributes[0].body.content, AttributeInner::Dg(dg) => {
+            assert_matches!(&dg.inner.content[0].value, DgInner::To(to) => {
+                assert_eq!(to.value.to_token_stream().to_string(), "\"meta.created_at\"");
+            });
+        });
+    });
+}
+
+#[test]
+fn it_parses_dg_mirror_from() {
+    let input = quote! {
+        #[dg(mirror_from)]
+        pub owner: Option<DbUser>
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::MirrorFrom(_));
+    });
+}
+
+#[test]
+fn it_parses_dg_as_try_mirror() {
+    let input = quote! {
+        #[dg(as_try_mirror)]
+        pub price: Cents
+    };
+
+    let mut it = input.to_token_iter();
+    let parsed = it.parse::<StructField>().expect("Failed to parse field");
+
+    assert_matches!(&parsed.attributes[0].body.content, AttributeInner::Dg(dg) => {
+        assert_matches!(&dg.inner.content[0].value, DgInner::AsTryMirror(_));
+    });
+}
@@ -1,9 +1,14 @@
 use doppleganger_macros_parse::{
-    AdtDecl, AttributeInner, Cons, DgInner, DgMap, EndOfStream, ModPath, Struct, StructField,
+    AdtDecl, Attribute, AttributeInner, Cons, DgDirection, DgFor, DgInner, DgMap, DgMapBack,
+    DgType, EndOfStream, Enum, EnumVariantData, GenericParams, IgnoreInner, ModPath, RenameInner,
+    Struct, StructKind, VerbatimUntil, Vis, WhereClauses,
 };
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use unsynn::*;
 
+#[cfg(test)]
+mod tests;
+
 #[proc_macro_derive(Doppleganger, attributes(dg))]
 pub fn macros(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     dg_macros(input.into()).into()
@@ -16,238 +21,2785 @@ fn dg_macros(input: TokenStream) -> TokenStream {
     match i.parse::<Cons<AdtDecl, EndOfStream>>() {
         Ok(it) => match it.first {
             AdtDecl::Struct(parsed) => process_struct(parsed),
-            AdtDecl::Enum(_parsed) => todo!("Not yet implemented"),
+            AdtDecl::Enum(parsed) => process_enum(parsed),
         },
         Err(err) => {
-            panic!("Could not parse type declaration: {err}");
+            let span = err
+                .failed_at()
+                .map_or_else(Span::call_site, |token| token.span());
+            let mut errors = Errors::default();
+            errors.push(span, format!("Could not parse type declaration: {err}"));
+            errors.into_token_stream()
         }
     }
 }
 
+/// Errors found while expanding a derive. They are collected so that one expansion reports
+/// all of them, each as a `compile_error!` pointing at the offending tokens.
+#[derive(Default)]
+struct Errors(Vec<TokenStream>);
+
+impl Errors {
+    fn push(&mut self, span: Span, message: impl std::fmt::Display) {
+        use quote::quote_spanned;
+
+        let message = Literal::string(&message.to_string());
+        self.0
+            .push(quote_spanned! {span=> ::core::compile_error!(#message); });
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn into_token_stream(self) -> TokenStream {
+        self.0.into_iter().collect()
+    }
+}
+
 fn process_struct(s: Struct) -> TokenStream {
     use doppleganger_macros_parse::ToTokens;
-    use quote::{format_ident, quote};
+    use quote::{format_ident, quote, quote_spanned};
 
-    // Find the dg attribute with direction
-    let mut direction = None;
-    for attr in &s.attributes {
-        if let doppleganger_macros_parse::AttributeInner::Dg(dg_attr) = &attr.body.content {
-            for item in dg_attr.inner.content.iter() {
-                if let doppleganger_macros_parse::DgInner::Direction(dir) = &item.value {
-                    direction = Some(dir);
-                    break;
-                }
-            }
+    let mut errors = Errors::default();
+    validate_dg_attributes(&s.attributes, DgPlace::Struct, &mut errors);
+    let type_params = type_params(s.generics.as_ref());
+    let passes = Pass::all(&s.attributes, &type_params);
+    check_dg_fallible(&s.attributes, &passes, &mut errors);
+    check_dg_ref(&s.attributes, &passes, &mut errors);
+    let patch = find_dg_patch(&s.attributes);
+    if passes.is_empty() && patch.is_none() {
+        errors.push(
+            s.name.span(),
+            "Missing #[dg(forward = ...)] or #[dg(backward = ...)] attribute on struct",
+        );
+    }
+    let two_way = passes.iter().any(|p| p.forward) && passes.iter().any(|p| !p.forward);
+    let targets = dg_targets(&passes, patch.map(|(_, path)| path));
+
+    let struct_name = &s.name;
+    let (generic_params_ts, generic_names_ts) = generics_ts(s.generics.as_ref());
+    let self_ty = quote! { #struct_name #generic_names_ts };
+
+    // Get the fields from the struct, tuple fields are addressed by their index
+    let fields: Vec<MirrorField> = match &s.kind {
+        StructKind::Struct { fields, .. } => fields
+            .content
+            .iter()
+            .map(|f| MirrorField {
+                attributes: &f.value.attributes,
+                vis: f.value._vis.to_token_stream(),
+                member: f.value.name.to_token_stream(),
+                typ: f.value.typ.to_token_stream(),
+            })
+            .collect(),
+        StructKind::TupleStruct { fields, .. } => fields
+            .content
+            .iter()
+            .enumerate()
+            .map(|(i, f)| MirrorField {
+                attributes: &f.value.attributes,
+                vis: f.value.vis.to_token_stream(),
+                member: Literal::usize_unsuffixed(i).to_token_stream(),
+                typ: f.value.typ.to_token_stream(),
+            })
+            .collect(),
+        StructKind::UnitStruct { .. } => Vec::new(),
+    };
+    let is_tuple = matches!(s.kind, StructKind::TupleStruct { .. });
+    let clauses = match &s.kind {
+        StructKind::Struct { clauses, .. }
+        | StructKind::TupleStruct { clauses, .. }
+        | StructKind::UnitStruct { clauses, .. } => clauses.as_ref(),
+    };
+    for field in &fields {
+        validate_dg_attributes(field.attributes, DgPlace::Field, &mut errors);
+        check_dg_for(field.attributes, &targets, &mut errors);
+        check_dg_map_pair(field.attributes, two_way, &mut errors);
+        check_dg_try_map(field.attributes, &passes, &mut errors);
+        check_dg_mirror_from(field.attributes, &passes, &mut errors);
+        check_dg_as_try_mirror(field.attributes, &passes, &mut errors);
+        if is_tuple
+            && let Some(flatten) =
+                dg_inners(field.attributes).find(|inner| matches!(inner, DgInner::Flatten(_)))
+        {
+            errors.push(
+                flatten.span(),
+                "#[dg(flatten(...))] is only supported on named fields",
+            );
         }
-        if direction.is_some() {
-            break;
+        check_dg_nested(field.attributes, &passes, is_tuple, &mut errors);
+    }
+    if !is_tuple {
+        check_dg_to_parents(&s.attributes, &fields, &passes, &mut errors);
+    }
+    let generate = find_dg_generate(&s.attributes);
+    if let Some((inner, _)) = generate {
+        check_dg_generate(inner, &fields, &mut errors);
+        if s.generics.is_some() {
+            errors.push(
+                s.name.span(),
+                "#[dg(generate = ...)] is not supported on generic types",
+            );
         }
     }
+    check_dg_generate_derive(&s.attributes, &mut errors);
+    check_dg_patch(&s.attributes, &mut errors);
+    check_dg_compute(
+        &s.attributes,
+        fields.iter().map(|f| f.attributes),
+        &passes,
+        &mut errors,
+    );
+    check_dg_bound(
+        &s.attributes,
+        &passes,
+        fields.iter().map(|f| (f.attributes, &f.typ)),
+        &mut errors,
+    );
+    let bound = parse_dg_bound(&s.attributes, &mut errors);
+    if !errors.is_empty() {
+        return errors.into_token_stream();
+    }
+    let generated = generate
+        .map(|(_, target)| generate_struct(&s._vis, &s.attributes, target, &fields, &s.kind));
+    let patched = patch.map(|(_, target)| patch_struct(&s, target, &fields, &self_ty));
 
-    let Some(direction) = direction else {
-        panic!("Missing #[dg(forward = ...)] or #[dg(backward = ...)] attribute on struct");
-    };
+    let is_unit = matches!(s.kind, StructKind::UnitStruct { .. });
 
-    let struct_name = &s.name;
+    passes
+        .iter()
+        .map(|pass| {
+            // #[dg(default)] on the struct fills everything the source does not provide when
+            // the struct is built, or when a unit struct builds the other type. Other forward
+            // passes have to provide every field of the other type.
+            let rest = if has_dg_default(&s.attributes) && (!pass.forward || is_unit) {
+                quote! { ..::core::default::Default::default() }
+            } else {
+                quote! {}
+            };
 
-    // Extract generic params for the impl block as TokenStream
-    let generic_params_ts = if let Some(generics) = &s.generics {
-        let params_ts = generics.params.to_token_stream();
-        quote! { < #params_ts > }
-    } else {
-        quote! {}
-    };
+            // For forward: Source = Self, Dest = OtherType, for backward the reverse.
+            // If field has rename/index, the renamed name is used on the other type.
+            // Tuple fields keep their position among the non-skipped fields.
+            let field_transforms = fields
+                .iter()
+                .filter(|f| {
+                    !field_is_skipped(f.attributes, pass.path, pass.forward)
+                        && field_get_dg_flatten(f.attributes).is_none()
+                })
+                .enumerate()
+                .map(|(i, field)| {
+                    let field_name = &field.member;
+                    // #[dg(from = "a.b")] and #[dg(to = "a.b")] replace the other name with
+                    // a path into the nested fields of the other type
+                    let nested = field_get_dg_nested(field.attributes, pass.forward);
+                    let other_name = match &nested {
+                        Some(path) => quote! { #(#path).* },
+                        None => field.other_member(i, is_tuple, pass.path),
+                    };
+                    let (source_name, dest_name) = if pass.forward {
+                        (field_name, &other_name)
+                    } else {
+                        (&other_name, field_name)
+                    };
+                    let name = match &nested {
+                        Some(path) if !pass.forward => path
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join("."),
+                        _ => member_name(source_name),
+                    };
 
-    // Extract just the names for use in the type
-    let generic_names_ts = if let Some(generics) = &s.generics {
-        let names_ts: Vec<TokenStream> = generics
-            .params
-            .iter()
-            .map(|p| match &p.value {
-                doppleganger_macros_parse::GenericParam::Lifetime { name, .. } => {
-                    name.to_token_stream()
+                    let value = mirror_value(
+                        field.attributes,
+                        field.typ.clone(),
+                        quote! { source.#source_name },
+                        &name,
+                        pass,
+                    );
+                    // a nested destination is first filled with its default, the first
+                    // segment of the path is the field of the destination holding it
+                    let parent = nested
+                        .filter(|path| pass.forward && path.len() > 1)
+                        .map(|path| (path[0].clone(), field_get_dg_to_span(field.attributes)));
+                    (dest_name.clone(), value, parent)
+                })
+                .collect::<Vec<_>>();
+
+            // Computed fields read the whole source, so they are evaluated before any of its
+            // fields are moved: #[dg(compute = ...)] fields backward, #[dg(extra(...))] forward
+            let computed: Vec<(TokenStream, TokenStream)> = if pass.forward {
+                dg_inners(&s.attributes)
+                    .filter_map(|inner| match inner {
+                        DgInner::Extra(extra) => Some(extra.fields.content.iter()),
+                        _ => None,
+                    })
+                    .flatten()
+                    .map(|f| {
+                        (
+                            f.value.name.to_token_stream(),
+                            f.value.expr.to_token_stream(),
+                        )
+                    })
+                    .collect()
+            } else {
+                fields
+                    .iter()
+                    .filter_map(|field| {
+                        let compute = field_get_dg_compute(field.attributes)?;
+                        Some((field.member.clone(), compute.to_token_stream()))
+                    })
+                    .collect()
+            };
+            let source_ref = pass.source_ref();
+            let computed_bindings: Vec<_> = (0..computed.len())
+                .map(|i| format_ident!("__dg_computed_{}", i))
+                .collect();
+            let computations: Vec<_> = computed
+                .iter()
+                .zip(&computed_bindings)
+                .map(|((_, compute), binding)| quote! { let #binding = (#compute)(#source_ref); })
+                .collect();
+            let computed_members: Vec<_> = computed.iter().map(|(member, _)| member).collect();
+
+            // Fields the source does not have are filled from their #[dg(default)]
+            let defaulted_fields = fields.iter().filter(|_| !pass.forward).filter_map(|field| {
+                let member = &field.member;
+                field_get_dg_default(field.attributes).map(|value| quote! { #member: #value })
+            });
+
+            // Flattened fields spread into, or are rebuilt from, the fields of the flat type
+            let flattened_fields = fields.iter().filter_map(|field| {
+                let nested = field_get_dg_flatten(field.attributes)?;
+                let member = &field.member;
+                Some(if pass.forward {
+                    let values = nested.iter().map(|nested| {
+                        pass.convert_flattened(
+                            field.attributes,
+                            pass.owned(quote! { source.#member.#nested }),
+                        )
+                    });
+                    quote! {
+                        #(#nested: #values),*
+                    }
+                } else {
+                    let ctor = expr_path(&field.typ);
+                    let values = nested.iter().map(|nested| {
+                        pass.convert_flattened(
+                            field.attributes,
+                            pass.owned(quote! { source.#nested }),
+                        )
+                    });
+                    quote! {
+                        #member: #ctor {
+                            #(#nested: #values),*
+                        }
+                    }
+                })
+            });
+
+            // In place, flattened fields are assigned one by one so that the rest of a
+            // nested destination is kept
+            let flattened_assignments = fields.iter().filter_map(|field| {
+                let nested = field_get_dg_flatten(field.attributes)?;
+                let member = &field.member;
+                Some(if pass.forward {
+                    let values = nested.iter().map(|nested| {
+                        pass.convert_flattened(field.attributes, quote! { source.#member.#nested })
+                    });
+                    quote! {
+                        #(dest.#nested = #values;)*
+                    }
+                } else {
+                    let values = nested.iter().map(|nested| {
+                        pass.convert_flattened(field.attributes, quote! { source.#nested })
+                    });
+                    quote! {
+                        #(dest.#member.#nested = #values;)*
+                    }
+                })
+            });
+
+            let where_clause_ts = where_clause_ts(
+                clauses,
+                s.generics.as_ref(),
+                bound.as_ref(),
+                pass,
+                fields
+                    .iter()
+                    .filter(|f| {
+                        !field_is_skipped(f.attributes, pass.path, pass.forward)
+                            && field_get_dg_flatten(f.attributes).is_none()
+                            && field_uses_convert(f.attributes, pass)
+                    })
+                    .map(|f| &f.typ),
+            );
+
+            let dest_ctor = pass.dest_ctor();
+            let mut nested_parents: Vec<TokenStream> = Vec::new();
+            let mut parent_names: Vec<&Ident> = Vec::new();
+            for (parent, span) in field_transforms
+                .iter()
+                .filter_map(|(_, _, parent)| parent.as_ref())
+            {
+                if !parent_names.contains(&parent) {
+                    parent_names.push(parent);
+                    nested_parents.push(quote_spanned! {*span=>
+                        #parent: <_ as ::doppleganger::NestedDefault>::nested_default()
+                    });
                 }
-                doppleganger_macros_parse::GenericParam::Type { name, .. } => {
-                    name.to_token_stream()
+            }
+            let is_nested: Vec<bool> = field_transforms
+                .iter()
+                .map(|(_, _, parent)| parent.is_some())
+                .collect();
+            let (dest_names, values): (Vec<_>, Vec<_>) = field_transforms
+                .into_iter()
+                .map(|(dest_name, value, _)| (dest_name, value))
+                .unzip();
+            // `MirrorInto` and `MirrorIntoFrom` overwrite the mirrored fields and keep the
+            // defaulted ones
+            let assignments: Vec<TokenStream> = dest_names
+                .iter()
+                .zip(&values)
+                .map(|(dest_name, value)| quote! { dest.#dest_name = #value; })
+                .chain(flattened_assignments)
+                .chain(
+                    computed_members
+                        .iter()
+                        .zip(&computed_bindings)
+                        .map(|(member, binding)| quote! { dest.#member = #binding; }),
+                )
+                .collect();
+            let into_body = if assignments.is_empty() {
+                quote! { let _ = (source, dest); }
+            } else {
+                quote! {
+                    #(#computations)*
+                    #(#assignments)*
                 }
-                doppleganger_macros_parse::GenericParam::Const { name, .. } => {
-                    name.to_token_stream()
+            };
+            // Nested destinations are assigned once the destination is built
+            let build = |field_values: &[TokenStream]| {
+                let (ctor_names, ctor_values) =
+                    split_nested(&dest_names, field_values, &is_nested, false);
+                let (nested_names, nested_values) =
+                    split_nested(&dest_names, field_values, &is_nested, true);
+                let ctor = quote! {
+                    #dest_ctor {
+                        #(#ctor_names: #ctor_values,)*
+                        #(#nested_parents,)*
+                        #(#computed_members: #computed_bindings,)*
+                        #(#defaulted_fields,)*
+                        #(#flattened_fields,)*
+                        #rest
+                    }
+                };
+                if nested_names.is_empty() {
+                    ctor
+                } else {
+                    quote! {{
+                        let mut dest = #ctor;
+                        #(dest.#nested_names = #nested_values;)*
+                        dest
+                    }}
                 }
+            };
+            // #[dg(collect_errors)] converts every field before giving up, each one in a
+            // closure so that its `?` hands the error to the collector
+            let body = if pass.collect && !values.is_empty() {
+                let bindings: Vec<TokenStream> = (0..values.len())
+                    .map(|i| format_ident!("__dg_field_{}", i).to_token_stream())
+                    .collect();
+                let dest = build(&bindings);
+                quote! {{
+                    let mut __dg_errors = ::doppleganger::ErrorCollector::default();
+                    #(
+                        #[allow(clippy::needless_question_mark)]
+                        let #bindings = __dg_errors.collect(|| ::core::result::Result::Ok(#values));
+                    )*
+                    let (#(::core::option::Option::Some(#bindings),)*) = (#(#bindings,)*) else {
+                        return ::core::result::Result::Err(__dg_errors.into_error());
+                    };
+                    #dest
+                }}
+            } else {
+                build(&values)
+            };
+            // the struct default may have nothing left to fill when the other type has no
+            // more fields than the mirrored ones
+            let body = if rest.is_empty() {
+                body
+            } else {
+                quote! {{
+                    #[allow(clippy::needless_update)]
+                    let dest = #body;
+                    dest
+                }}
+            };
+            let body = if computations.is_empty() {
+                body
+            } else {
+                quote! {{
+                    #(#computations)*
+                    #body
+                }}
+            };
+            pass.impl_block(
+                &generic_params_ts,
+                &self_ty,
+                &where_clause_ts,
+                body,
+                into_body,
+            )
+        })
+        .chain(generated)
+        .chain(patched)
+        .collect()
+}
+
+/// Declare the `#[dg(generate = ...)]` type: the fields mirrored to it with their renames,
+/// docs and visibility, each typed as the `Mirror::Dest` of the derived field
+fn generate_struct(
+    vis: &Option<Vis>,
+    attributes: &[Attribute],
+    target: &DgType,
+    fields: &[MirrorField],
+    kind: &StructKind,
+) -> TokenStream {
+    use doppleganger_macros_parse::ToTokens;
+    use quote::quote;
+
+    let vis = vis.to_token_stream();
+    let is_tuple = matches!(kind, StructKind::TupleStruct { .. });
+    let docs = doc_attributes(attributes);
+    let derives = dg_inners(attributes)
+        .filter_map(|inner| match inner {
+            DgInner::GenerateDerive(generate_derive) => Some(generate_derive),
+            _ => None,
+        })
+        .map(|generate_derive| {
+            let derives = generate_derive
+                .derives
+                .content
+                .iter()
+                .map(|d| d.value.to_token_stream());
+            quote! { #[derive(#(#derives),*)] }
+        });
+    let fields: Vec<TokenStream> = fields
+        .iter()
+        .filter(|f| !field_is_skipped(f.attributes, target, true))
+        .enumerate()
+        .map(|(i, field)| {
+            let docs = doc_attributes(field.attributes);
+            let vis = &field.vis;
+            let typ = &field.typ;
+            let typ = quote! { <#typ as ::doppleganger::Mirror>::Dest };
+            if is_tuple {
+                quote! { #(#docs)* #vis #typ }
+            } else {
+                let name = field.other_member(i, is_tuple, target);
+                quote! { #(#docs)* #vis #name: #typ }
+            }
+        })
+        .collect();
+    let target = target.to_token_stream();
+
+    match kind {
+        StructKind::Struct { .. } => quote! {
+            #(#docs)*
+            #(#derives)*
+            #vis struct #target {
+                #(#fields,)*
+            }
+        },
+        StructKind::TupleStruct { .. } => quote! {
+            #(#docs)*
+            #(#derives)*
+            #vis struct #target(#(#fields),*);
+        },
+        StructKind::UnitStruct { .. } => quote! {
+            #(#docs)*
+            #(#derives)*
+            #vis struct #target;
+        },
+    }
+}
+
+/// A struct field as seen by the code generation, tuple fields use their index as `member`
+struct MirrorField<'a> {
+    attributes: &'a [Attribute],
+    vis: TokenStream,
+    member: TokenStream,
+    typ: TokenStream,
+}
+
+impl MirrorField<'_> {
+    /// The member this field maps to on `target`. `position` is the index among the
+    /// non-ignored fields, used when a tuple struct mirrors another tuple struct.
+    fn other_member(&self, position: usize, is_tuple: bool, target: &DgType) -> TokenStream {
+        use doppleganger_macros_parse::ToTokens;
+        use quote::{format_ident, quote};
+
+        if let Some(rename) = field_get_dg_rename(self.attributes, target) {
+            let rename_ident = format_ident!("{}", rename);
+            quote! { #rename_ident }
+        } else if let Some(index) = field_get_dg_index(self.attributes) {
+            Literal::usize_unsuffixed(index).to_token_stream()
+        } else if is_tuple {
+            Literal::usize_unsuffixed(position).to_token_stream()
+        } else {
+            self.member.clone()
+        }
+    }
+}
+
+/// One generated conversion between the derived type and one of its targets
+struct Pass<'a> {
+    /// The other type of the conversion
+    path: &'a DgType,
+    /// Whether the derived type is the source of the conversion
+    forward: bool,
+    /// Whether the pass only implements `MirrorFrom`, because the derive has several
+    /// targets or this is the backward half of a `both` whose forward half is the `Mirror`
+    targeted: bool,
+    /// Whether the conversion is a `try_forward`/`try_backward` implementing `TryMirror`
+    fallible: bool,
+    /// Whether a `Mirror` impl also bridges to `TryMirror`, which is only possible when the
+    /// derive does not implement `TryMirror` itself
+    try_bridge: bool,
+    /// The `#[dg(error = ...)]` of a fallible conversion
+    error: Option<&'a DgType>,
+    /// Whether a fallible conversion reports every failed field and every failed element of
+    /// their containers, `#[dg(collect_errors)]`
+    collect: bool,
+    /// Whether the conversion implements `MirrorRef`, reading the source through a reference
+    by_ref: bool,
+    /// The type parameters of the derived type, their bounds are only inferred for fields
+    /// converted through the trait of the pass
+    type_params: &'a [Ident],
+}
+
+impl<'a> Pass<'a> {
+    /// one pass per `forward`/`backward` attribute, two for each `both`
+    fn all(attributes: &'a [Attribute], type_params: &'a [Ident]) -> Vec<Self> {
+        use doppleganger_macros_parse::ToTokens;
+
+        let directions: Vec<(&DgType, bool, bool)> = find_dg_directions(attributes)
+            .into_iter()
+            .flat_map(|direction| match direction {
+                DgDirection::Forward { path, .. } => vec![(path, true, false)],
+                DgDirection::Backward { path, .. } => vec![(path, false, false)],
+                DgDirection::Both { path, .. } => vec![(path, true, false), (path, false, false)],
+                DgDirection::TryForward { path, .. } => vec![(path, true, true)],
+                DgDirection::TryBackward { path, .. } => vec![(path, false, true)],
+                DgDirection::Generate { path, .. } => vec![(path, true, false)],
+            })
+            .collect();
+
+        // A single infallible target implements `Mirror` for its forward direction, or its
+        // backward one when there is none, the other direction of a `both` only `MirrorFrom`
+        let mut targets: Vec<String> = directions
+            .iter()
+            .filter(|(_, _, fallible)| !fallible)
+            .map(|(path, _, _)| path.to_token_stream().to_string())
+            .collect();
+        targets.sort();
+        targets.dedup();
+        let several = targets.len() > 1;
+        let has_forward = directions
+            .iter()
+            .any(|(_, forward, fallible)| *forward && !fallible);
+        let try_bridge = !directions.iter().any(|(_, _, fallible)| *fallible);
+        let error = dg_inners(attributes).find_map(|inner| match inner {
+            DgInner::Error(error) => Some(&error.path),
+            _ => None,
+        });
+        let collect = dg_inners(attributes).any(|inner| matches!(inner, DgInner::CollectErrors(_)));
+        let mut passes: Vec<Self> = directions
+            .into_iter()
+            .map(|(path, forward, fallible)| Self {
+                path,
+                forward,
+                targeted: !fallible && (several || (has_forward && !forward)),
+                fallible,
+                try_bridge,
+                error,
+                collect: collect && fallible,
+                by_ref: false,
+                type_params,
             })
             .collect();
-        quote! { < #(#names_ts),* > }
-    } else {
-        quote! {}
-    };
 
-    // Get the fields from the struct
-    let fields = match &s.kind {
-        doppleganger_macros_parse::StructKind::Struct { fields, .. } => fields,
-        doppleganger_macros_parse::StructKind::TupleStruct { .. } => {
-            panic!("Tuple structs not yet supported");
+        // #[dg(ref)] repeats the `Mirror` conversion from a reference
+        if dg_inners(attributes).any(|inner| matches!(inner, DgInner::Ref(_)))
+            && let Some(mirror) = passes.iter().find(|p| !p.targeted && !p.fallible)
+        {
+            passes.push(Self {
+                by_ref: true,
+                try_bridge: false,
+                ..*mirror
+            });
         }
-        doppleganger_macros_parse::StructKind::UnitStruct { .. } => {
-            panic!("Unit structs not yet supported");
+        passes
+    }
+
+    /// the value of a binding in a match on the source, which binds references when the
+    /// conversion is a `MirrorRef`
+    fn binding_value(&self, binding: &Ident) -> TokenStream {
+        use quote::quote;
+
+        if self.by_ref {
+            quote! { (*#binding) }
+        } else {
+            quote! { #binding }
         }
-    };
+    }
 
-    match direction {
-        doppleganger_macros_parse::DgDirection::Forward { path, .. } => {
-            // For forward: Source = Self, Dest = OtherType
-            // If field has rename, use renamed name in dest, original name in source
-            let field_transforms = fields
-                .content
-                .iter()
-                .filter(|f| !field_has_dg_ignore(&f.value))
-                .map(|field| {
-                    let field_name = &field.value.name;
-                    let field_type = &field.value.typ;
-                    let field_type_ts = field_type.to_token_stream();
-
-                    // For forward: rename specifies the destination field name
-                    let dest_field_name = if let Some(rename) = field_get_dg_rename(&field.value) {
-                        let rename_ident = format_ident!("{}", rename);
-                        quote! { #rename_ident }
-                    } else {
-                        quote! { #field_name }
-                    };
+    /// a reference to the whole source, handed to computed fields
+    fn source_ref(&self) -> TokenStream {
+        use quote::quote;
+
+        if self.by_ref {
+            quote! { source }
+        } else {
+            quote! { &source }
+        }
+    }
 
+    /// take `value` out of the source, which a `MirrorRef` conversion has to clone
+    fn owned(&self, value: TokenStream) -> TokenStream {
+        use quote::quote;
 
-                    match field_has_dg_map(&field.value) {
-                        None => {
-                            quote! {
-                                #dest_field_name: <#field_type_ts as ::doppleganger::Mirror>::mirror(source.#field_name)
-                            }
-                            
-                        },
-                        Some(path) => {
-                            let tokens = path.to_token_stream();
-                            quote! {
-                                #dest_field_name: <field_type_ts as ::doppleganger::Mirror>::mirror(#tokens(source.#field_name))
-                            }
-                        }
-                        
+        if self.by_ref {
+            quote! { ::core::clone::Clone::clone(&#value) }
+        } else {
+            value
+        }
+    }
+
+    /// the error type of a fallible conversion, `MirrorError` unless `#[dg(error = ...)]`
+    /// names one
+    fn error_ty(&self) -> TokenStream {
+        use doppleganger_macros_parse::ToTokens;
+        use quote::quote;
+
+        match self.error {
+            Some(error) => error.to_token_stream(),
+            None => quote! { ::doppleganger::MirrorError },
+        }
+    }
+
+    /// unwrap the `result` of converting the source field `name`, returning its error.
+    /// A `MirrorError` records the field in its path, a custom error is converted as is.
+    fn propagate(&self, result: TokenStream, name: &str) -> TokenStream {
+        use quote::quote;
+
+        if self.error.is_some() {
+            quote! { #result? }
+        } else {
+            quote! {
+                #result.map_err(|err| ::doppleganger::MirrorError::from(err).in_field(#name))?
+            }
+        }
+    }
+
+    /// the path used to construct the destination inside the generated fn
+    fn dest_ctor(&self) -> TokenStream {
+        use quote::quote;
+
+        if self.forward && !self.targeted {
+            quote! { Self::Dest }
+        } else {
+            quote! { Self }
+        }
+    }
+
+    /// determine if a field with `attributes` is converted through `MirrorFrom`, which every
+    /// field of a targeted pass is. `#[dg(mirror_from)]` asks for it in a `Mirror` pass, for
+    /// field types with several targets that do not implement `Mirror`.
+    fn uses_mirror_from(&self, attributes: &[Attribute]) -> bool {
+        !self.fallible && !self.by_ref && (self.targeted || field_has_dg_mirror_from(attributes))
+    }
+
+    /// convert `value`, the source field `name` of type `typ` on the derived type
+    fn convert(
+        &self,
+        attributes: &[Attribute],
+        typ: &TokenStream,
+        value: TokenStream,
+        name: &str,
+    ) -> TokenStream {
+        use quote::quote;
+
+        if self.fallible && field_has_dg_as_try_mirror(attributes) {
+            // `AsTryMirror` cannot fail, so its error needs no conversion
+            return quote! {
+                match <::doppleganger::AsTryMirror<#typ> as ::doppleganger::TryMirror>::try_mirror(#value) {
+                    ::core::result::Result::Ok(value) => value,
+                    ::core::result::Result::Err(never) => match never {},
+                }
+            };
+        }
+        if self.fallible {
+            // containers stop at their first failure unless the mirror collects every one
+            let try_mirror = if self.collect {
+                quote! { try_mirror_all }
+            } else {
+                quote! { try_mirror }
+            };
+            return self.propagate(
+                quote! { <#typ as ::doppleganger::TryMirror>::#try_mirror(#value) },
+                name,
+            );
+        }
+        if self.by_ref {
+            return quote! { <#typ as ::doppleganger::MirrorRef>::mirror_ref(&#value) };
+        }
+        match (self.uses_mirror_from(attributes), self.forward) {
+            (false, _) => quote! { <#typ as ::doppleganger::Mirror>::mirror(#value) },
+            (true, true) => quote! { <_ as ::doppleganger::MirrorFrom<#typ>>::mirror_from(#value) },
+            (true, false) => {
+                quote! { <#typ as ::doppleganger::MirrorFrom<_>>::mirror_from(#value) }
+            }
+        }
+    }
+
+    /// convert `value`, a field of a `#[dg(flatten(...))]` struct, whose type is unknown to
+    /// the derive and inferred from the field it is read from or written to
+    fn convert_flattened(&self, attributes: &[Attribute], value: TokenStream) -> TokenStream {
+        use quote::quote;
+
+        if self.uses_mirror_from(attributes) {
+            quote! { ::doppleganger::MirrorTo::mirror_to(#value) }
+        } else if self.forward {
+            quote! { ::doppleganger::mirror_as_source(#value) }
+        } else {
+            quote! { ::doppleganger::mirror_as_dest(#value) }
+        }
+    }
+
+    /// Wrap `body` in the impls for this pass. A single target implements `Mirror`, with
+    /// `into_body` as its `MirrorInto`, and bridges `MirrorFrom`, `MirrorIntoFrom` and an
+    /// infallible `TryMirror` to them, several targets and the backward half of a `both`
+    /// implement only `MirrorFrom`, with `into_body` as their `MirrorIntoFrom`. A fallible
+    /// pass implements only `TryMirror`, with the `?` of its fields converting their errors,
+    /// and a `#[dg(ref)]` pass only `MirrorRef`.
+    fn impl_block(
+        &self,
+        generic_params: &TokenStream,
+        self_ty: &TokenStream,
+        where_clause: &TokenStream,
+        body: TokenStream,
+        into_body: TokenStream,
+    ) -> TokenStream {
+        use doppleganger_macros_parse::ToTokens;
+        use quote::quote;
+
+        let path_ts = self.path.to_token_stream();
+        let (source, dest) = if self.forward {
+            (self_ty, &path_ts)
+        } else {
+            (&path_ts, self_ty)
+        };
+
+        if self.by_ref {
+            return quote! {
+                impl #generic_params ::doppleganger::MirrorRef for #self_ty #where_clause {
+                    type Source = #source;
+                    type Dest = #dest;
+
+                    fn mirror_ref(source: &Self::Source) -> Self::Dest {
+                        #body
                     }
+                }
+            };
+        }
 
-                });
+        if self.fallible {
+            let error = self.error_ty();
+            return quote! {
+                impl #generic_params ::doppleganger::TryMirror for #self_ty #where_clause {
+                    type Source = #source;
+                    type Dest = #dest;
+                    type Error = #error;
+
+                    fn try_mirror(
+                        source: Self::Source,
+                    ) -> ::core::result::Result<Self::Dest, Self::Error> {
+                        ::core::result::Result::Ok(#body)
+                    }
+                }
+            };
+        }
+
+        if self.targeted {
+            return quote! {
+                impl #generic_params ::doppleganger::MirrorFrom<#source> for #dest #where_clause {
+                    fn mirror_from(source: #source) -> Self {
+                        #body
+                    }
+                }
+
+                impl #generic_params ::doppleganger::MirrorIntoFrom<#source> for #dest #where_clause {
+                    fn mirror_into_from(source: #source, dest: &mut Self) {
+                        #into_body
+                    }
+                }
+            };
+        }
 
-            let path_ts = path.to_token_stream();
+        let try_bridge = if self.try_bridge {
             quote! {
-                impl #generic_params_ts ::doppleganger::Mirror for #struct_name #generic_names_ts {
-                    type Source = Self;
-                    type Dest = #path_ts;
+                impl #generic_params ::doppleganger::TryMirror for #self_ty #where_clause {
+                    type Source = #source;
+                    type Dest = #dest;
+                    type Error = ::core::convert::Infallible;
 
-                    fn mirror(source: Self::Source) -> Self::Dest {
-                        Self::Dest {
-                            #(#field_transforms),*
-                        }
+                    fn try_mirror(
+                        source: Self::Source,
+                    ) -> ::core::result::Result<Self::Dest, Self::Error> {
+                        ::core::result::Result::Ok(<#self_ty as ::doppleganger::Mirror>::mirror(source))
                     }
                 }
             }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            impl #generic_params ::doppleganger::Mirror for #self_ty #where_clause {
+                type Source = #source;
+                type Dest = #dest;
+
+                fn mirror(source: Self::Source) -> Self::Dest {
+                    #body
+                }
+            }
+
+            impl #generic_params ::doppleganger::MirrorInto for #self_ty #where_clause {
+                fn mirror_into(source: Self::Source, dest: &mut Self::Dest) {
+                    #into_body
+                }
+            }
+
+            impl #generic_params ::doppleganger::MirrorFrom<#source> for #dest #where_clause {
+                fn mirror_from(source: #source) -> Self {
+                    <#self_ty as ::doppleganger::Mirror>::mirror(source)
+                }
+            }
+
+            impl #generic_params ::doppleganger::MirrorIntoFrom<#source> for #dest #where_clause {
+                fn mirror_into_from(source: #source, dest: &mut Self) {
+                    <#self_ty as ::doppleganger::MirrorInto>::mirror_into(source, dest)
+                }
+            }
+
+            #try_bridge
         }
-        doppleganger_macros_parse::DgDirection::Backward { path, .. } => {
-            // For backward: Source = OtherType, Dest = Self
-            // If field has rename, use original name in dest, renamed name in source
-            let field_transforms = fields
+    }
+}
+
+fn process_enum(e: Enum) -> TokenStream {
+    use doppleganger_macros_parse::ToTokens;
+    use quote::{format_ident, quote};
+
+    let mut errors = Errors::default();
+    validate_dg_attributes(&e.attributes, DgPlace::Enum, &mut errors);
+    let type_params = type_params(e.generics.as_ref());
+    let passes = Pass::all(&e.attributes, &type_params);
+    check_dg_fallible(&e.attributes, &passes, &mut errors);
+    check_dg_ref(&e.attributes, &passes, &mut errors);
+    if passes.is_empty() {
+        errors.push(
+            e.name.span(),
+            "Missing #[dg(forward = ...)] or #[dg(backward = ...)] attribute on enum",
+        );
+    }
+    let two_way = passes.iter().any(|p| p.forward) && passes.iter().any(|p| !p.forward);
+    let targets = dg_targets(&passes, None);
+
+    let enum_name = &e.name;
+    let (generic_params_ts, generic_names_ts) = generics_ts(e.generics.as_ref());
+    let self_ty = quote! { #enum_name #generic_names_ts };
+
+    let mut variant_fields: Vec<(&[Attribute], TokenStream)> = Vec::new();
+    for variant in e.body.content.iter() {
+        let (attributes, fields): (_, Vec<(&[Attribute], TokenStream)>) =
+            match &variant.value.variant {
+                EnumVariantData::Unit(v) => (&v.attributes, Vec::new()),
+                EnumVariantData::Tuple(v) => (
+                    &v.attributes,
+                    v.fields
+                        .content
+                        .iter()
+                        .map(|f| (&f.value.attributes[..], f.value.typ.to_token_stream()))
+                        .collect(),
+                ),
+                EnumVariantData::Struct(v) => (
+                    &v.attributes,
+                    v.fields
+                        .content
+                        .iter()
+                        .map(|f| (&f.value.attributes[..], f.value.typ.to_token_stream()))
+                        .collect(),
+                ),
+            };
+        validate_dg_attributes(attributes, DgPlace::Variant, &mut errors);
+        check_dg_for(attributes, &targets, &mut errors);
+        for (attributes, _) in &fields {
+            validate_dg_attributes(attributes, DgPlace::VariantField, &mut errors);
+            check_dg_for(attributes, &targets, &mut errors);
+            check_dg_map_pair(attributes, two_way, &mut errors);
+            check_dg_try_map(attributes, &passes, &mut errors);
+            check_dg_mirror_from(attributes, &passes, &mut errors);
+            check_dg_as_try_mirror(attributes, &passes, &mut errors);
+        }
+        variant_fields.extend(fields);
+    }
+    check_dg_bound(
+        &e.attributes,
+        &passes,
+        variant_fields
+            .iter()
+            .map(|(attributes, typ)| (*attributes, typ)),
+        &mut errors,
+    );
+    let bound = parse_dg_bound(&e.attributes, &mut errors);
+    if !errors.is_empty() {
+        return errors.into_token_stream();
+    }
+
+    passes
+        .iter()
+        .map(|pass| {
+            let path_ts = expr_path(&pass.path.to_token_stream());
+
+            // The derived side of each arm always uses the declared names, the other side
+            // uses the renamed ones. Forward matches on the derived enum and builds the other
+            // type, backward the reverse.
+            let arms: Vec<TokenStream> = e
+                .body
                 .content
                 .iter()
-                .filter(|f| !field_has_dg_ignore(&f.value))
-                .map(|field| {
-                    let field_name = &field.value.name;
-                    let field_type = &field.value.typ;
-                    let field_type_ts = field_type.to_token_stream();
-
-                    // For backward: rename specifies the source field name
-                    let source_field_name = if let Some(rename) = field_get_dg_rename(&field.value) {
-                        let rename_ident = format_ident!("{}", rename);
-                        quote! { #rename_ident }
+                .map(|variant| {
+                    let (attributes, variant_name) = match &variant.value.variant {
+                        EnumVariantData::Unit(v) => (&v.attributes, &v.name),
+                        EnumVariantData::Tuple(v) => (&v.attributes, &v.name),
+                        EnumVariantData::Struct(v) => (&v.attributes, &v.name),
+                    };
+                    let other_name = match field_get_dg_rename(attributes, pass.path) {
+                        Some(rename) => format_ident!("{}", rename),
+                        None => variant_name.clone(),
+                    };
+                    let (source_path, dest_path) = if pass.forward {
+                        (
+                            quote! { #enum_name::#variant_name },
+                            quote! { #path_ts::#other_name },
+                        )
                     } else {
-                        quote! { #field_name }
+                        (
+                            quote! { #path_ts::#other_name },
+                            quote! { #enum_name::#variant_name },
+                        )
                     };
 
-                    match field_has_dg_map(&field.value) {
-                        None => {
+                    match &variant.value.variant {
+                        EnumVariantData::Unit(_) => quote! {
+                            #source_path => #dest_path
+                        },
+                        EnumVariantData::Tuple(v) => {
+                            let mut bindings = Vec::new();
+                            let mut values = Vec::new();
+                            for (i, f) in v.fields.content.iter().enumerate() {
+                                if !pass.forward
+                                    && let Some(value) = field_get_dg_default(&f.value.attributes)
+                                {
+                                    values.push(value);
+                                    continue;
+                                }
+                                if field_is_skipped(&f.value.attributes, pass.path, pass.forward) {
+                                    if pass.forward {
+                                        bindings.push(quote! { _ });
+                                    }
+                                    continue;
+                                }
+                                let binding = format_ident!("field_{}", i);
+                                values.push(mirror_value(
+                                    &f.value.attributes,
+                                    f.value.typ.to_token_stream(),
+                                    pass.binding_value(&binding),
+                                    &i.to_string(),
+                                    pass,
+                                ));
+                                bindings.push(quote! { #binding });
+                            }
                             quote! {
-                                #field_name: <#field_type_ts as ::doppleganger::Mirror>::mirror(source.#source_field_name)
+                                #source_path(#(#bindings),*) => #dest_path(#(#values),*)
                             }
-                        },
-                        Some(path) => {
-                            let tokens = path.to_token_stream();
+                        }
+                        EnumVariantData::Struct(v) => {
+                            let fields = v.fields.content.iter().filter(|f| {
+                                !field_is_skipped(&f.value.attributes, pass.path, pass.forward)
+                            });
+                            let defaulted_fields = v
+                                .fields
+                                .content
+                                .iter()
+                                .filter(|_| !pass.forward)
+                                .filter_map(|f| {
+                                    let field_name = &f.value.name;
+                                    field_get_dg_default(&f.value.attributes)
+                                        .map(|value| quote! { #field_name: #value })
+                                });
+                            let (bindings, values): (Vec<_>, Vec<_>) = fields
+                                .map(|f| {
+                                    let field_name = &f.value.name;
+                                    let other_name =
+                                        match field_get_dg_rename(&f.value.attributes, pass.path) {
+                                            Some(rename) => format_ident!("{}", rename),
+                                            None => field_name.clone(),
+                                        };
+                                    let (source_name, dest_name) = if pass.forward {
+                                        (field_name, &other_name)
+                                    } else {
+                                        (&other_name, field_name)
+                                    };
+                                    let value = mirror_value(
+                                        &f.value.attributes,
+                                        f.value.typ.to_token_stream(),
+                                        pass.binding_value(source_name),
+                                        &member_name(&source_name.to_token_stream()),
+                                        pass,
+                                    );
+                                    (quote! { #source_name }, quote! { #dest_name: #value })
+                                })
+                                .unzip();
                             quote! {
-                                #field_name: #tokens(source.#source_field_name)
+                                #source_path { #(#bindings,)* .. } => #dest_path {
+                                    #(#values,)*
+                                    #(#defaulted_fields,)*
+                                }
                             }
                         }
                     }
-                });
+                })
+                .collect();
 
-            let path_ts = path.to_token_stream();
-            quote! {
-                impl #generic_params_ts ::doppleganger::Mirror for #struct_name #generic_names_ts {
-                    type Source = #path_ts;
-                    type Dest = Self;
+            let mirrored_types: Vec<TokenStream> = e
+                .body
+                .content
+                .iter()
+                .flat_map(|variant| match &variant.value.variant {
+                    EnumVariantData::Unit(_) => Vec::new(),
+                    EnumVariantData::Tuple(v) => v
+                        .fields
+                        .content
+                        .iter()
+                        .map(|f| (&f.value.attributes, f.value.typ.to_token_stream()))
+                        .collect(),
+                    EnumVariantData::Struct(v) => v
+                        .fields
+                        .content
+                        .iter()
+                        .map(|f| (&f.value.attributes, f.value.typ.to_token_stream()))
+                        .collect(),
+                })
+                .filter(|(attributes, _)| {
+                    !field_is_skipped(attributes, pass.path, pass.forward)
+                        && field_uses_convert(attributes, pass)
+                })
+                .map(|(_, typ)| typ)
+                .collect();
+            let where_clause_ts = where_clause_ts(
+                e.clauses.as_ref(),
+                e.generics.as_ref(),
+                bound.as_ref(),
+                pass,
+                &mirrored_types,
+            );
 
-                    fn mirror(source: Self::Source) -> Self::Dest {
-                        Self {
-                            #(#field_transforms),*
-                        }
-                    }
+            // a variant cannot be updated in place, `MirrorInto` replaces the whole value
+            let body = quote! {
+                match source {
+                    #(#arms),*
                 }
-            }
+            };
+            pass.impl_block(
+                &generic_params_ts,
+                &self_ty,
+                &where_clause_ts,
+                body.clone(),
+                quote! {
+                    *dest = #body;
+                },
+            )
+        })
+        .collect()
+}
+
+/// mirror a single value of type `typ`, applying any `#[dg(map = ...)]`,
+/// `#[dg(map_back = ...)]`, `#[dg(with = ...)]` or, when fallible, `#[dg(try_map = ...)]`
+/// on it. `name` is the field on the source side, recorded in the path of a `MirrorError`.
+fn mirror_value(
+    attributes: &[Attribute],
+    typ: TokenStream,
+    value: TokenStream,
+    name: &str,
+    pass: &Pass,
+) -> TokenStream {
+    use doppleganger_macros_parse::ToTokens;
+    use quote::quote;
+
+    // The map output is the destination value, unless #[dg(map_then_mirror)] asks for it
+    // to be mirrored as well. A map/map_back pair uses the map matching the direction.
+    // Maps are arbitrary expressions, parenthesized so closures can be called in place.
+    // #[dg(with = module)] calls the module fn named after the direction.
+    // #[dg(try_map = ...)] only applies to fallible passes, its error is converted by `?`.
+    let map = if pass.fallible
+        && let Some(try_map) = field_get_dg_try_map(attributes)
+    {
+        let try_map = try_map.to_token_stream();
+        let mapped = pass.propagate(quote! { (#try_map)(#value) }, name);
+        return if field_has_dg_map_then_mirror(attributes) {
+            pass.convert(attributes, &typ, mapped, name)
+        } else {
+            mapped
+        };
+    } else if let Some(module) = field_get_dg_with(attributes) {
+        let module = module.to_token_stream();
+        if pass.forward {
+            quote! { #module::forward }
+        } else {
+            quote! { #module::backward }
+        }
+    } else {
+        match (
+            field_has_dg_map(attributes),
+            field_has_dg_map_back(attributes),
+        ) {
+            (Some(map), _) if pass.forward => map.to_token_stream(),
+            (_, Some(map_back)) => map_back.to_token_stream(),
+            (Some(map), None) => map.to_token_stream(),
+            (None, None) => return pass.convert(attributes, &typ, value, name),
         }
+    };
+    // maps take the field by value, a `MirrorRef` conversion hands them a clone
+    let value = pass.owned(value);
+    if field_has_dg_map_then_mirror(attributes) {
+        pass.convert(attributes, &typ, quote! { (#map)(#value) }, name)
+    } else {
+        quote! { (#map)(#value) }
     }
 }
 
-/// determine if a field should be ignored
-fn field_has_dg_ignore(field: &StructField) -> bool {
-    field
-        .attributes
-        .iter()
-        .any(|attr| match &attr.body.content {
-            AttributeInner::Dg(attr) => attr
-                .inner
-                .content
-                .iter()
-                .any(|inner| matches!(inner.value, DgInner::Ignore(_))),
-            _ => false,
-        })
+/// determine if `mirror_value` converts the field through the trait of `pass`, rather than
+/// only handing it to a map
+fn field_uses_convert(attributes: &[Attribute], pass: &Pass) -> bool {
+    if pass.fallible && field_get_dg_try_map(attributes).is_some() {
+        return field_has_dg_map_then_mirror(attributes);
+    }
+    field_has_dg_map_then_mirror(attributes)
+        || (field_has_dg_map(attributes).is_none()
+            && field_has_dg_map_back(attributes).is_none()
+            && field_get_dg_with(attributes).is_none())
 }
 
-fn field_has_dg_map(field: &StructField) -> Option<&ModPath> {
-    field
-        .attributes
-        .iter()
-        .find_map(|attr| match &attr.body.content {
-            AttributeInner::Dg(attr) => {
-                attr.inner
-                    .content
-                    .iter()
-                    .find_map(|inner| match &inner.value {
-                        DgInner::Map(DgMap { path, .. }) => Some(path),
-                        _ => None,
-                    })
-            }
-            _ => None,
-        })
+/// A map must be paired with a map_back when the derive mirrors in both directions, and a
+/// map_back is meaningless otherwise
+fn check_dg_map_pair(attributes: &[Attribute], two_way: bool, errors: &mut Errors) {
+    // a field skipped in one direction is only mirrored in the other
+    let two_way = two_way
+        && !dg_inners(attributes)
+            .any(|inner| matches!(inner, DgInner::SkipForward(_) | DgInner::SkipBackward(_)));
+    let map = dg_inners(attributes).find(|inner| matches!(inner, DgInner::Map(_)));
+    let map_back = dg_inners(attributes).find(|inner| matches!(inner, DgInner::MapBack(_)));
+    if map.is_none()
+        && map_back.is_none()
+        && field_get_dg_with(attributes).is_none()
+        && field_get_dg_try_map(attributes).is_none()
+        && let Some(then_mirror) =
+            dg_inners(attributes).find(|inner| matches!(inner, DgInner::MapThenMirror(_)))
+    {
+        errors.push(
+            then_mirror.span(),
+            "#[dg(map_then_mirror)] needs a #[dg(map = ...)], #[dg(with = ...)] or #[dg(try_map = ...)] to apply to",
+        );
+    }
+    match (map, map_back, two_way) {
+        (Some(map), None, true) => errors.push(
+            map.span(),
+            "#[dg(map = ...)] needs a matching #[dg(map_back = ...)] when mirroring in both directions",
+        ),
+        (None, Some(map_back), true) => errors.push(
+            map_back.span(),
+            "#[dg(map_back = ...)] needs a matching #[dg(map = ...)] when mirroring in both directions",
+        ),
+        (_, Some(map_back), false) => errors.push(
+            map_back.span(),
+            "#[dg(map_back = ...)] is only allowed when mirroring in both directions",
+        ),
+        _ => {}
+    }
+}
+
+/// A derive implements `TryMirror` at most once, and `error` only makes sense when it does
+fn check_dg_fallible(attributes: &[Attribute], passes: &[Pass], errors: &mut Errors) {
+    let mut fallible = dg_inners(attributes).filter(|inner| {
+        matches!(
+            inner,
+            DgInner::Direction(DgDirection::TryForward { .. } | DgDirection::TryBackward { .. })
+        )
+    });
+    if fallible.next().is_some()
+        && let Some(extra) = fallible.next()
+    {
+        errors.push(
+            extra.span(),
+            "Only one #[dg(try_forward = ...)] or #[dg(try_backward = ...)] is allowed per type",
+        );
+    }
+    if !passes.iter().any(|pass| pass.fallible)
+        && let Some(error) = dg_inners(attributes).find(|inner| matches!(inner, DgInner::Error(_)))
+    {
+        errors.push(
+            error.span(),
+            "#[dg(error = ...)] needs a #[dg(try_forward = ...)] or #[dg(try_backward = ...)]",
+        );
+    }
+    if !passes.iter().any(|pass| pass.fallible)
+        && let Some(collect) =
+            dg_inners(attributes).find(|inner| matches!(inner, DgInner::CollectErrors(_)))
+    {
+        errors.push(
+            collect.span(),
+            "#[dg(collect_errors)] needs a #[dg(try_forward = ...)] or #[dg(try_backward = ...)]",
+        );
+    }
 }
 
-/// get the renamed field name if present
-fn field_get_dg_rename(field: &StructField) -> Option<String> {
+/// Declare the `#[dg(patch = ...)]` type, with an optional field for every field that is
+/// not ignored for it, and its `apply` writing the fields that are present into the derived
+/// type. A field that is already optional becomes an `Option<Option<T>>`, which can clear it.
+fn patch_struct(
+    s: &Struct,
+    target: &DgType,
+    fields: &[MirrorField],
+    self_ty: &TokenStream,
+) -> TokenStream {
     use doppleganger_macros_parse::ToTokens;
+    use quote::{format_ident, quote};
 
-    field
-        .attributes
-        .iter()
-        .find_map(|attr| match &attr.body.content {
-            AttributeInner::Dg(attr) => attr.inner.content.iter().find_map(|inner| {
-                if let DgInner::Rename(rename) = &inner.value {
-                    // Extract the string value from the LiteralString
-                    let value_str = rename.value.to_token_stream().to_string();
-                    // Remove surrounding quotes
-                    Some(value_str.trim_matches('"').to_string())
-                } else {
-                    None
-                }
-            }),
+    let vis = s._vis.to_token_stream();
+    let is_tuple = matches!(s.kind, StructKind::TupleStruct { .. });
+    let docs = doc_attributes(&s.attributes);
+    let derives = dg_inners(&s.attributes)
+        .filter_map(|inner| match inner {
+            DgInner::PatchDerive(patch_derive) => Some(patch_derive),
             _ => None,
         })
+        .map(|patch_derive| {
+            let derives = patch_derive
+                .derives
+                .content
+                .iter()
+                .map(|d| d.value.to_token_stream());
+            quote! { #[derive(#(#derives),*)] }
+        });
+    let clauses = match &s.kind {
+        StructKind::Struct { clauses, .. }
+        | StructKind::TupleStruct { clauses, .. }
+        | StructKind::UnitStruct { clauses, .. } => clauses.as_ref(),
+    };
+    let fields: Vec<_> = fields
+        .iter()
+        .filter(|f| !field_has_dg_ignore(f.attributes, target))
+        .collect();
+    let generics = patch_generics(
+        s.generics.as_ref(),
+        clauses,
+        fields.iter().map(|field| &field.typ),
+    );
+    let PatchGenerics {
+        declaration: declared_generics,
+        impl_params: generic_params,
+        impl_names: generic_names,
+        impl_clauses: clauses,
+        method_params,
+        method_clauses,
+    } = &generics;
+
+    let (declarations, assignments): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let docs = doc_attributes(field.attributes);
+            let vis = &field.vis;
+            let typ = &field.typ;
+            let member = &field.member;
+            let patch_member = if is_tuple {
+                Literal::usize_unsuffixed(i).to_token_stream()
+            } else if let Some(rename) = field_get_dg_rename(field.attributes, target) {
+                format_ident!("{}", rename).to_token_stream()
+            } else {
+                member.clone()
+            };
+            let declaration = if is_tuple {
+                quote! { #(#docs)* #vis ::core::option::Option<#typ> }
+            } else {
+                quote! { #(#docs)* #vis #patch_member: ::core::option::Option<#typ> }
+            };
+            let assignment = quote! {
+                if let ::core::option::Option::Some(value) = self.#patch_member {
+                    target.#member = value;
+                }
+            };
+            (declaration, assignment)
+        })
+        .unzip();
+
+    let target = target.to_token_stream();
+    let declaration = match &s.kind {
+        StructKind::Struct { .. } => quote! {
+            #vis struct #target #declared_generics #clauses {
+                #(#declarations,)*
+            }
+        },
+        StructKind::TupleStruct { .. } => quote! {
+            #vis struct #target #declared_generics (#(#declarations),*) #clauses;
+        },
+        StructKind::UnitStruct { .. } => quote! {
+            #vis struct #target #declared_generics #clauses;
+        },
+    };
+    let apply = if assignments.is_empty() {
+        quote! { let _ = target; }
+    } else {
+        quote! { #(#assignments)* }
+    };
+
+    quote! {
+        #(#docs)*
+        #(#derives)*
+        #declaration
+
+        impl #generic_params #target #generic_names #clauses {
+            /// Write the fields that are present into `target`, keeping the others.
+            #vis fn apply #method_params (self, target: &mut #self_ty) #method_clauses {
+                #apply
+            }
+        }
+    }
+}
+
+/// The generics of a `#[dg(patch = ...)]` type and its `apply`, see [`patch_generics`]
+struct PatchGenerics {
+    declaration: TokenStream,
+    impl_params: TokenStream,
+    impl_names: TokenStream,
+    impl_clauses: TokenStream,
+    method_params: TokenStream,
+    method_clauses: TokenStream,
+}
+
+/// Split the generics of the derived type between the patch type and its `apply`. A parameter
+/// that no patched field uses would be rejected as unused on the patch type, so it is only
+/// declared on `apply`, along with the where predicates that mention it.
+fn patch_generics<'t>(
+    generics: Option<&GenericParams>,
+    clauses: Option<&WhereClauses>,
+    field_types: impl IntoIterator<Item = &'t TokenStream>,
+) -> PatchGenerics {
+    use doppleganger_macros_parse::{GenericParam, ToTokens};
+    use quote::quote;
+
+    let params: Vec<_> = generics
+        .into_iter()
+        .flat_map(|g| g.params.iter())
+        .map(|p| {
+            let name = match &p.value {
+                GenericParam::Lifetime { name, .. } => name.name.clone(),
+                GenericParam::Type { name, .. } | GenericParam::Const { name, .. } => name.clone(),
+            };
+            (name, &p.value)
+        })
+        .collect();
+
+    // a parameter is kept when a patched field or the declaration of a kept parameter uses it
+    let mut uses: TokenStream = field_types.into_iter().cloned().collect();
+    let mut kept = vec![false; params.len()];
+    while let Some(i) = (0..params.len()).find(|&i| !kept[i] && mentions_ident(&uses, &params[i].0))
+    {
+        kept[i] = true;
+        uses.extend(params[i].1.to_token_stream());
+    }
+    let is_dropped = |tokens: &TokenStream| {
+        params
+            .iter()
+            .zip(&kept)
+            .any(|((name, _), kept)| !kept && mentions_ident(tokens, name))
+    };
+
+    let mut declaration = Vec::new();
+    let (mut impl_params, mut impl_names, mut method_params) = (Vec::new(), Vec::new(), Vec::new());
+    for ((_, param), kept) in params.iter().zip(&kept) {
+        let (param_ts, name_ts) = generic_param_ts(param);
+        if *kept {
+            declaration.push(param.to_token_stream());
+            impl_params.push(param_ts);
+            impl_names.push(name_ts);
+        } else {
+            method_params.push(param_ts);
+        }
+    }
+    let (impl_clauses, method_clauses): (Vec<_>, Vec<_>) = clauses
+        .into_iter()
+        .flat_map(|c| c.clauses.iter())
+        .map(|c| c.value.to_token_stream())
+        .partition(|clause| !is_dropped(clause));
+
+    let angled = |items: &[TokenStream]| {
+        if items.is_empty() {
+            quote! {}
+        } else {
+            quote! { < #(#items),* > }
+        }
+    };
+    let clauses = |items: &[TokenStream]| {
+        if items.is_empty() {
+            quote! {}
+        } else {
+            quote! { where #(#items),* }
+        }
+    };
+    PatchGenerics {
+        declaration: angled(&declaration),
+        impl_params: angled(&impl_params),
+        impl_names: angled(&impl_names),
+        impl_clauses: clauses(&impl_clauses),
+        method_params: angled(&method_params),
+        method_clauses: clauses(&method_clauses),
+    }
+}
+
+/// The type declared by `#[dg(generate = ...)]` must be a plain name and its fields need a
+/// type, which is unknown for mapped or flattened fields
+fn check_dg_generate(generate: &DgInner, fields: &[MirrorField], errors: &mut Errors) {
+    use doppleganger_macros_parse::ToTokens;
+
+    if let DgInner::Direction(DgDirection::Generate { path, .. }) = generate
+        && !matches!(
+            path.to_token_stream().into_iter().collect::<Vec<_>>()[..],
+            [TokenTree::Ident(_)]
+        )
+    {
+        errors.push(
+            generate.span(),
+            "#[dg(generate = ...)] needs the plain name of the type to declare",
+        );
+    }
+    for field in fields {
+        for inner in dg_inners(field.attributes) {
+            if matches!(
+                inner,
+                DgInner::Flatten(_)
+                    | DgInner::Index(_)
+                    | DgInner::Map(_)
+                    | DgInner::MapBack(_)
+                    | DgInner::With(_)
+                    | DgInner::To(_)
+            ) {
+                errors.push(
+                    inner.span(),
+                    format!(
+                        "#[dg({})] is not supported on the fields of a #[dg(generate = ...)] struct",
+                        dg_key(inner)
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// The type declared by `#[dg(patch = ...)]` must be a plain name, and `patch_derive` only
+/// applies to it
+fn check_dg_patch(attributes: &[Attribute], errors: &mut Errors) {
+    use doppleganger_macros_parse::ToTokens;
+
+    if let Some((patch, path)) = find_dg_patch(attributes)
+        && !matches!(
+            path.to_token_stream().into_iter().collect::<Vec<_>>()[..],
+            [TokenTree::Ident(_)]
+        )
+    {
+        errors.push(
+            patch.span(),
+            "#[dg(patch = ...)] needs the plain name of the type to declare",
+        );
+    }
+    if find_dg_patch(attributes).is_none()
+        && let Some(patch_derive) =
+            dg_inners(attributes).find(|inner| matches!(inner, DgInner::PatchDerive(_)))
+    {
+        errors.push(
+            patch_derive.span(),
+            "#[dg(patch_derive(...))] needs a #[dg(patch = ...)]",
+        );
+    }
+}
+
+/// `generate_derive` only applies to a generated type
+fn check_dg_generate_derive(attributes: &[Attribute], errors: &mut Errors) {
+    if find_dg_generate(attributes).is_none()
+        && let Some(generate_derive) =
+            dg_inners(attributes).find(|inner| matches!(inner, DgInner::GenerateDerive(_)))
+    {
+        errors.push(
+            generate_derive.span(),
+            "#[dg(generate_derive(...))] needs a #[dg(generate = ...)]",
+        );
+    }
+}
+
+/// `compute` fields are filled when mirroring backward and `extra` fields when mirroring
+/// forward into a type that is not generated, since the type of an extra field is unknown
+fn check_dg_compute<'f>(
+    attributes: &[Attribute],
+    fields: impl IntoIterator<Item = &'f [Attribute]>,
+    passes: &[Pass],
+    errors: &mut Errors,
+) {
+    if !passes.iter().any(|pass| !pass.forward) {
+        for attributes in fields {
+            if let Some(compute) =
+                dg_inners(attributes).find(|inner| matches!(inner, DgInner::Compute(_)))
+            {
+                errors.push(
+                    compute.span(),
+                    "#[dg(compute = ...)] needs a #[dg(backward = ...)] or #[dg(try_backward = ...)]",
+                );
+            }
+        }
+    }
+    if let Some(extra) = dg_inners(attributes).find(|inner| matches!(inner, DgInner::Extra(_))) {
+        if !passes.iter().any(|pass| pass.forward) {
+            errors.push(
+                extra.span(),
+                "#[dg(extra(...))] needs a #[dg(forward = ...)] or #[dg(try_forward = ...)]",
+            );
+        }
+        if find_dg_generate(attributes).is_some() {
+            errors.push(
+                extra.span(),
+                "#[dg(extra(...))] cannot be combined with #[dg(generate = ...)]",
+            );
+        }
+    }
+}
+
+/// `from` is read when mirroring backward and `to` written when mirroring forward, both
+/// name a dotted path of named fields
+fn check_dg_nested(attributes: &[Attribute], passes: &[Pass], is_tuple: bool, errors: &mut Errors) {
+    for inner in dg_inners(attributes) {
+        let (value, forward) = match inner {
+            DgInner::From(from) => (&from.value, false),
+            DgInner::To(to) => (&to.value, true),
+            _ => continue,
+        };
+        let key = dg_key(inner);
+        if is_tuple {
+            errors.push(
+                inner.span(),
+                format!("#[dg({key} = ...)] is only supported on named fields"),
+            );
+        }
+        if !passes.iter().any(|pass| pass.forward == forward) {
+            let (direction, try_direction) = if forward {
+                ("forward", "try_forward")
+            } else {
+                ("backward", "try_backward")
+            };
+            errors.push(
+                inner.span(),
+                format!(
+                    "#[dg({key} = ...)] needs a #[dg({direction} = ...)] or #[dg({try_direction} = ...)]"
+                ),
+            );
+        }
+        if !value.as_str().split('.').all(is_identifier) {
+            errors.push(
+                inner.span(),
+                format!(
+                    "#[dg({key} = ...)] needs a dotted path of field names, e.g. \"address.city\""
+                ),
+            );
+        }
+    }
+}
+
+/// The parent of a nested `to` is built from its default and then filled by the fields
+/// targeting it, so it cannot also be mapped as a whole
+fn check_dg_to_parents(
+    attributes: &[Attribute],
+    fields: &[MirrorField],
+    passes: &[Pass],
+    errors: &mut Errors,
+) {
+    let mut reported = Vec::new();
+    for pass in passes.iter().filter(|pass| pass.forward) {
+        let mapped: Vec<String> = fields
+            .iter()
+            .filter(|f| {
+                !field_is_skipped(f.attributes, pass.path, true)
+                    && field_get_dg_flatten(f.attributes).is_none()
+            })
+            .filter_map(|f| match field_get_dg_nested(f.attributes, true) {
+                Some(path) if path.len() > 1 => None,
+                Some(path) => Some(path[0].to_string()),
+                None => Some(
+                    field_get_dg_rename(f.attributes, pass.path)
+                        .unwrap_or_else(|| f.member.to_string()),
+                ),
+            })
+            .chain(
+                fields
+                    .iter()
+                    .filter_map(|f| field_get_dg_flatten(f.attributes))
+                    .flatten()
+                    .map(ToString::to_string),
+            )
+            .chain(
+                dg_inners(attributes)
+                    .filter_map(|inner| match inner {
+                        DgInner::Extra(extra) => Some(extra.fields.content.iter()),
+                        _ => None,
+                    })
+                    .flatten()
+                    .map(|f| f.value.name.to_string()),
+            )
+            .collect();
+        for (i, field) in fields.iter().enumerate() {
+            if reported.contains(&i) || field_is_skipped(field.attributes, pass.path, true) {
+                continue;
+            }
+            if let Some(path) = field_get_dg_nested(field.attributes, true)
+                && path.len() > 1
+                && mapped.contains(&path[0].to_string())
+            {
+                reported.push(i);
+                errors.push(
+                    field_get_dg_to_span(field.attributes),
+                    format!(
+                        "`{}` is mapped from another field and cannot also be the parent of a #[dg(to = ...)] field",
+                        path[0]
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Bounds are not inferred for `MirrorFrom` and `AsTryMirror` conversions, the field types of
+/// the other type are unknown, so converting a generic field through one needs an explicit
+/// `#[dg(bound = "...")]`
+fn check_dg_bound<'f>(
+    attributes: &[Attribute],
+    passes: &[Pass],
+    fields: impl IntoIterator<Item = (&'f [Attribute], &'f TokenStream)>,
+    errors: &mut Errors,
+) {
+    if dg_inners(attributes).any(|inner| matches!(inner, DgInner::Bound(_))) {
+        return;
+    }
+    for (attributes, typ) in fields {
+        let Some(pass) = passes.iter().find(|pass| {
+            (pass.uses_mirror_from(attributes)
+                || (pass.fallible && field_has_dg_as_try_mirror(attributes)))
+                && !field_is_skipped(attributes, pass.path, pass.forward)
+                && field_get_dg_flatten(attributes).is_none()
+                && field_uses_convert(attributes, pass)
+        }) else {
+            continue;
+        };
+        if let Some(param) = pass.type_params.iter().find(|p| mentions_ident(typ, p)) {
+            let span = typ
+                .clone()
+                .into_iter()
+                .next()
+                .map_or_else(Span::call_site, |token| token.span());
+            let subject = if pass.fallible {
+                "a #[dg(as_try_mirror)] field"
+            } else if pass.targeted {
+                "a type with several targets or a `both`"
+            } else {
+                "a #[dg(mirror_from)] field"
+            };
+            errors.push(
+                span,
+                format!(
+                    "The bounds on `{param}` cannot be inferred for {subject}, add them with #[dg(bound = \"...\")]"
+                ),
+            );
+        }
+    }
+}
+
+/// the types a `for = ...` restriction can name: the other type of every pass and the
+/// `#[dg(patch = ...)]` type, as displayed in messages
+fn dg_targets(passes: &[Pass], patch: Option<&DgType>) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for target in passes.iter().map(|pass| pass.path).chain(patch) {
+        let target = display_tokens(target);
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    targets
+}
+
+/// A `for = ...` restriction has to name one of the `targets`, a misspelled one would
+/// silently never apply
+fn check_dg_for(attributes: &[Attribute], targets: &[String], errors: &mut Errors) {
+    use doppleganger_macros_parse::ToTokens;
+
+    for inner in dg_inners(attributes) {
+        let target = match inner {
+            DgInner::Rename(RenameInner {
+                target: Some(target),
+                ..
+            })
+            | DgInner::Ignore(IgnoreInner {
+                target: Some(target),
+                ..
+            }) => &target.content.path,
+            _ => continue,
+        };
+        let name = display_tokens(target);
+        if targets.contains(&name) {
+            continue;
+        }
+        let span = target
+            .to_token_stream()
+            .into_iter()
+            .next()
+            .map_or_else(|| inner.span(), |token| token.span());
+        let expected = if targets.is_empty() {
+            String::new()
+        } else {
+            format!(", expected one of {}", targets.join(", "))
+        };
+        errors.push(
+            span,
+            format!(
+                "#[dg({}(for = {name}))] names a type this derive does not mirror{expected}",
+                dg_key(inner)
+            ),
+        );
+    }
+}
+
+/// `ref` repeats the `Mirror` impl, which only exists for a single infallible target
+fn check_dg_ref(attributes: &[Attribute], passes: &[Pass], errors: &mut Errors) {
+    if !passes.iter().any(|pass| pass.by_ref)
+        && let Some(by_ref) = dg_inners(attributes).find(|inner| matches!(inner, DgInner::Ref(_)))
+    {
+        errors.push(
+            by_ref.span(),
+            "#[dg(ref)] needs exactly one #[dg(forward = ...)] or #[dg(backward = ...)]",
+        );
+    }
+}
+
+/// `mirror_from` changes how an infallible mirror by value converts the field, which a map
+/// only does with `map_then_mirror`
+fn check_dg_mirror_from(attributes: &[Attribute], passes: &[Pass], errors: &mut Errors) {
+    if let Some(mirror_from) =
+        dg_inners(attributes).find(|inner| matches!(inner, DgInner::MirrorFrom(_)))
+        && !passes
+            .iter()
+            .any(|pass| !pass.fallible && !pass.by_ref && field_uses_convert(attributes, pass))
+    {
+        errors.push(
+            mirror_from.span(),
+            "#[dg(mirror_from)] only applies to a field converted by a #[dg(forward = ...)], #[dg(backward = ...)] or #[dg(both = ...)] mirror",
+        );
+    }
+}
+
+/// `as_try_mirror` changes how a fallible mirror converts the field, which a map only does
+/// with `map_then_mirror`
+fn check_dg_as_try_mirror(attributes: &[Attribute], passes: &[Pass], errors: &mut Errors) {
+    if let Some(as_try_mirror) =
+        dg_inners(attributes).find(|inner| matches!(inner, DgInner::AsTryMirror(_)))
+        && !passes
+            .iter()
+            .any(|pass| pass.fallible && field_uses_convert(attributes, pass))
+    {
+        errors.push(
+            as_try_mirror.span(),
+            "#[dg(as_try_mirror)] only applies to a field converted by a #[dg(try_forward = ...)] or #[dg(try_backward = ...)] mirror",
+        );
+    }
+}
+
+/// `try_map` is only used by a fallible mirror
+fn check_dg_try_map(attributes: &[Attribute], passes: &[Pass], errors: &mut Errors) {
+    if !passes.iter().any(|pass| pass.fallible)
+        && let Some(try_map) =
+            dg_inners(attributes).find(|inner| matches!(inner, DgInner::TryMap(_)))
+    {
+        errors.push(
+            try_map.span(),
+            "#[dg(try_map = ...)] needs a #[dg(try_forward = ...)] or #[dg(try_backward = ...)]",
+        );
+    }
+}
+
+/// Every key a `#[dg(...)]` attribute accepts, with its syntax
+const DG_KEYS: &[(&str, &str)] = &[
+    ("forward", "forward = Type"),
+    ("backward", "backward = Type"),
+    ("both", "both = Type"),
+    ("default", "default` or `default = expr"),
+    (
+        "rename",
+        "rename = \"name\"` or `rename(for = Type) = \"name\"",
+    ),
+    ("from", "from = \"field.nested\""),
+    ("to", "to = \"field.nested\""),
+    ("index", "index = 0"),
+    ("bound", "bound = \"T: Trait\""),
+    ("flatten", "flatten(field, ...)"),
+    ("ignore", "ignore` or `ignore(for = Type)"),
+    ("skip_forward", "skip_forward"),
+    ("skip_backward", "skip_backward"),
+    ("map", "map = expr"),
+    ("map_back", "map_back = expr"),
+    ("map_then_mirror", "map_then_mirror"),
+    ("mirror_from", "mirror_from"),
+    ("with", "with = path::to::module"),
+    ("try_forward", "try_forward = Type"),
+    ("try_backward", "try_backward = Type"),
+    ("try_map", "try_map = expr"),
+    ("as_try_mirror", "as_try_mirror"),
+    ("error", "error = Type"),
+    ("collect_errors", "collect_errors"),
+    ("ref", "ref"),
+    ("generate", "generate = Type"),
+    ("generate_derive", "generate_derive(Trait, ...)"),
+    ("patch", "patch = Type"),
+    ("patch_derive", "patch_derive(Trait, ...)"),
+    ("compute", "compute = path::to::fn"),
+    ("extra", "extra(field = path::to::fn, ...)"),
+];
+
+/// Where a `#[dg(...)]` attribute is written
+#[derive(Clone, Copy, PartialEq)]
+enum DgPlace {
+    Struct,
+    Enum,
+    Field,
+    Variant,
+    VariantField,
+}
+
+impl DgPlace {
+    const ALL: [Self; 5] = [
+        Self::Struct,
+        Self::Enum,
+        Self::Field,
+        Self::Variant,
+        Self::VariantField,
+    ];
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Struct => "structs",
+            Self::Enum => "enums",
+            Self::Field => "struct fields",
+            Self::Variant => "enum variants",
+            Self::VariantField => "enum variant fields",
+        }
+    }
+
+    /// the keys that have an effect here, `default = expr` only fills fields
+    fn keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Enum => &[
+                "forward",
+                "backward",
+                "both",
+                "try_forward",
+                "try_backward",
+                "bound",
+                "error",
+                "ref",
+            ],
+            Self::Struct => &[
+                "forward",
+                "backward",
+                "both",
+                "try_forward",
+                "try_backward",
+                "generate",
+                "default",
+                "bound",
+                "error",
+                "collect_errors",
+                "ref",
+                "generate_derive",
+                "patch",
+                "patch_derive",
+                "extra",
+            ],
+            Self::Field => &[
+                "default",
+                "default = expr",
+                "rename",
+                "from",
+                "to",
+                "index",
+                "flatten",
+                "ignore",
+                "skip_forward",
+                "skip_backward",
+                "map",
+                "map_back",
+                "map_then_mirror",
+                "mirror_from",
+                "with",
+                "try_map",
+                "as_try_mirror",
+                "compute",
+            ],
+            Self::Variant => &["rename"],
+            Self::VariantField => &[
+                "default",
+                "default = expr",
+                "rename",
+                "ignore",
+                "skip_forward",
+                "skip_backward",
+                "map",
+                "map_back",
+                "map_then_mirror",
+                "mirror_from",
+                "with",
+                "try_map",
+                "as_try_mirror",
+            ],
+        }
+    }
+}
+
+/// Keys that make no sense next to the listed keys on the same item
+const DG_CONFLICTS: &[(&str, &[&str])] = &[
+    (
+        "ignore",
+        &[
+            "default",
+            "rename",
+            "index",
+            "flatten",
+            "map",
+            "map_back",
+            "with",
+            "try_map",
+            "mirror_from",
+            "as_try_mirror",
+            "skip_forward",
+            "skip_backward",
+            "compute",
+            "from",
+            "to",
+        ],
+    ),
+    (
+        "default",
+        &[
+            "rename",
+            "index",
+            "flatten",
+            "map",
+            "map_back",
+            "with",
+            "mirror_from",
+            "try_map",
+            "as_try_mirror",
+            "skip_forward",
+            "compute",
+            "from",
+            "to",
+        ],
+    ),
+    (
+        "flatten",
+        &[
+            "rename",
+            "index",
+            "map",
+            "map_back",
+            "with",
+            "try_map",
+            "as_try_mirror",
+            "skip_forward",
+            "skip_backward",
+            "compute",
+            "from",
+            "to",
+        ],
+    ),
+    (
+        "compute",
+        &[
+            "rename",
+            "index",
+            "map",
+            "map_back",
+            "with",
+            "try_map",
+            "mirror_from",
+            "as_try_mirror",
+            "skip_forward",
+            "skip_backward",
+            "from",
+            "to",
+        ],
+    ),
+    ("from", &["rename", "index", "skip_backward"]),
+    ("to", &["rename", "index", "skip_forward"]),
+    ("with", &["map", "map_back", "try_map"]),
+    ("error", &["collect_errors"]),
+    ("skip_forward", &["skip_backward"]),
+];
+
+/// the key of a `#[dg(...)]` item as written by the user
+fn dg_key(inner: &DgInner) -> String {
+    match inner {
+        DgInner::Direction(DgDirection::Forward { .. }) => "forward".into(),
+        DgInner::Direction(DgDirection::Backward { .. }) => "backward".into(),
+        DgInner::Direction(DgDirection::Both { .. }) => "both".into(),
+        DgInner::Direction(DgDirection::TryForward { .. }) => "try_forward".into(),
+        DgInner::Direction(DgDirection::TryBackward { .. }) => "try_backward".into(),
+        DgInner::Direction(DgDirection::Generate { .. }) => "generate".into(),
+        DgInner::DefaultEquals(_) | DgInner::Default(_) => "default".into(),
+        DgInner::Rename(_) => "rename".into(),
+        DgInner::From(_) => "from".into(),
+        DgInner::To(_) => "to".into(),
+        DgInner::Index(_) => "index".into(),
+        DgInner::Bound(_) => "bound".into(),
+        DgInner::Flatten(_) => "flatten".into(),
+        DgInner::Ignore(_) => "ignore".into(),
+        DgInner::SkipForward(_) => "skip_forward".into(),
+        DgInner::SkipBackward(_) => "skip_backward".into(),
+        DgInner::Map(_) => "map".into(),
+        DgInner::MapBack(_) => "map_back".into(),
+        DgInner::MapThenMirror(_) => "map_then_mirror".into(),
+        DgInner::MirrorFrom(_) => "mirror_from".into(),
+        DgInner::With(_) => "with".into(),
+        DgInner::TryMap(_) => "try_map".into(),
+        DgInner::AsTryMirror(_) => "as_try_mirror".into(),
+        DgInner::Error(_) => "error".into(),
+        DgInner::CollectErrors(_) => "collect_errors".into(),
+        DgInner::Ref(_) => "ref".into(),
+        DgInner::GenerateDerive(_) => "generate_derive".into(),
+        DgInner::Patch(_) => "patch".into(),
+        DgInner::PatchDerive(_) => "patch_derive".into(),
+        DgInner::Compute(_) => "compute".into(),
+        DgInner::Extra(_) => "extra".into(),
+        DgInner::Unknown(unknown) => unknown.key.to_string(),
+    }
+}
+
+/// Report unknown or malformed keys, keys placed where they have no effect, repeated keys
+/// and keys that conflict with each other in the `#[dg(...)]` attributes of one item.
+/// Without this the helpers reading the attributes would silently use the first match.
+fn validate_dg_attributes(attributes: &[Attribute], place: DgPlace, errors: &mut Errors) {
+    use doppleganger_macros_parse::ToTokens;
+
+    for attr in attributes {
+        if let AttributeInner::Any(tokens) = &attr.body.content
+            && let Some(TokenTree::Ident(ident)) = tokens.first()
+            && ident == "dg"
+        {
+            errors.push(ident.span(), "Could not parse #[dg(...)] attribute");
+        }
+    }
+
+    for inner in dg_inners(attributes) {
+        let key = match inner {
+            DgInner::Unknown(_) => continue,
+            DgInner::DefaultEquals(_) => "default = expr".to_string(),
+            _ => dg_key(inner),
+        };
+        if !place.keys().contains(&key.as_str()) {
+            let mut allowed: Vec<_> = DgPlace::ALL
+                .into_iter()
+                .filter(|other| other.keys().contains(&key.as_str()))
+                .map(DgPlace::describe)
+                .collect();
+            let last = allowed.pop().unwrap_or_default();
+            let allowed = if allowed.is_empty() {
+                last.to_string()
+            } else {
+                format!("{} or {last}", allowed.join(", "))
+            };
+            errors.push(
+                inner.span(),
+                format!(
+                    "#[dg({key})] is not supported on {}, only on {allowed}",
+                    place.describe(),
+                ),
+            );
+        }
+    }
+
+    // renames become identifiers of the generated code
+    for inner in dg_inners(attributes) {
+        if let DgInner::Rename(rename) = inner
+            && !is_identifier(rename.value.as_str())
+        {
+            errors.push(
+                inner.span(),
+                format!(
+                    "#[dg(rename = ...)] needs a field or variant name, found {:?}",
+                    rename.value.as_str()
+                ),
+            );
+        }
+    }
+
+    // what each item sets, items targeting different types do not collide
+    let mut seen = Vec::new();
+    for inner in dg_inners(attributes) {
+        let slots = match inner {
+            DgInner::Unknown(unknown) => {
+                let key = unknown.key.to_string();
+                let message = match DG_KEYS.iter().find(|(name, _)| *name == key) {
+                    // a derive only sees its own item, so the nested fields have to be listed
+                    Some((_, syntax)) if key == "flatten" => format!(
+                        "Invalid #[dg({key})] attribute, expected `{syntax}` listing the fields of the nested struct, which the derive cannot see"
+                    ),
+                    Some((_, syntax)) => {
+                        format!("Invalid #[dg({key})] attribute, expected `{syntax}`")
+                    }
+                    None => {
+                        let keys: Vec<_> = DG_KEYS.iter().map(|(name, _)| *name).collect();
+                        let suggestion = DG_KEYS
+                            .iter()
+                            .map(|(name, _)| (edit_distance(&key, name), name))
+                            .filter(|(distance, _)| *distance <= (key.len() / 3).max(2))
+                            .min()
+                            .map(|(_, name)| format!(", did you mean `{name}`?"))
+                            .unwrap_or_else(|| ".".into());
+                        format!(
+                            "Unknown #[dg({key})] attribute{suggestion} Valid keys are: {}",
+                            keys.join(", ")
+                        )
+                    }
+                };
+                errors.push(inner.span(), message);
+                continue;
+            }
+            DgInner::Direction(DgDirection::Forward { path, .. }) => {
+                vec![format!("forward = {}", display_tokens(path))]
+            }
+            DgInner::Direction(DgDirection::Backward { path, .. }) => {
+                vec![format!("backward = {}", display_tokens(path))]
+            }
+            DgInner::Direction(DgDirection::TryForward { path, .. }) => {
+                vec![format!("try_forward = {}", display_tokens(path))]
+            }
+            DgInner::Direction(DgDirection::TryBackward { path, .. }) => {
+                vec![format!("try_backward = {}", display_tokens(path))]
+            }
+            DgInner::Direction(DgDirection::Both { path, .. }) => vec![
+                format!("forward = {}", display_tokens(path)),
+                format!("backward = {}", display_tokens(path)),
+            ],
+            DgInner::Rename(RenameInner {
+                target: Some(target),
+                ..
+            }) => vec![format!(
+                "rename(for = {})",
+                display_tokens(&target.content.path)
+            )],
+            DgInner::Ignore(IgnoreInner {
+                target: Some(target),
+                ..
+            }) => vec![format!(
+                "ignore(for = {})",
+                display_tokens(&target.content.path)
+            )],
+            _ => vec![dg_key(inner)],
+        };
+        for slot in slots {
+            if seen.contains(&slot) {
+                errors.push(inner.span(), format!("Duplicate #[dg({slot})]"));
+            } else {
+                seen.push(slot);
+            }
+        }
+    }
+
+    // `skip_backward` with a default is written forward like any other field
+    let written_forward =
+        dg_inners(attributes).any(|inner| matches!(inner, DgInner::SkipBackward(_)));
+    for inner in dg_inners(attributes) {
+        let key = dg_key(inner);
+        let conflict = dg_inners(attributes).find(|other| match other {
+            DgInner::Default(_) | DgInner::DefaultEquals(_) if written_forward => false,
+            // a rename for another target is fine next to a restricted ignore
+            DgInner::Ignore(IgnoreInner {
+                target: Some(ignored),
+                ..
+            }) => matches!(inner, DgInner::Rename(RenameInner { target, .. })
+            if target.as_ref().is_none_or(|target| {
+                target.content.path.to_token_stream().to_string()
+                    == ignored.content.path.to_token_stream().to_string()
+            })),
+            other => DG_CONFLICTS
+                .iter()
+                .any(|(first, keys)| dg_key(other) == *first && keys.contains(&key.as_str())),
+        });
+        if let Some(conflict) = conflict {
+            errors.push(
+                inner.span(),
+                format!(
+                    "#[dg({key})] cannot be combined with #[dg({})]",
+                    dg_key(conflict)
+                ),
+            );
+        }
+    }
+}
+
+/// determine if `name` can be turned into an identifier, `r#type` included
+fn is_identifier(name: &str) -> bool {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && name != "_"
+}
+
+/// the name of a field in the path of a `MirrorError`, `type` for `r#type` and `0` for the
+/// first tuple field
+fn member_name(member: &TokenStream) -> String {
+    let name = member.to_string();
+    name.strip_prefix("r#").map(str::to_owned).unwrap_or(name)
+}
+
+/// render tokens for a message, `api::Paged<T>` rather than `api :: Paged < T >`
+fn display_tokens(tokens: &impl doppleganger_macros_parse::ToTokens) -> String {
+    tokens
+        .to_token_stream()
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" < ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+}
+
+/// the number of single character edits needed to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// every item of every `#[dg(...)]` attribute
+fn dg_inners(attributes: &[Attribute]) -> impl Iterator<Item = &DgInner> {
+    attributes
+        .iter()
+        .filter_map(|attr| match &attr.body.content {
+            AttributeInner::Dg(attr) => Some(attr.inner.content.iter()),
+            _ => None,
+        })
+        .flatten()
+        .map(|inner| &inner.value)
+}
+
+/// determine if a bare `#[dg(default)]` is present
+fn has_dg_default(attributes: &[Attribute]) -> bool {
+    attributes.iter().any(|attr| match &attr.body.content {
+        AttributeInner::Dg(attr) => attr
+            .inner
+            .content
+            .iter()
+            .any(|inner| matches!(inner.value, DgInner::Default(_))),
+        _ => false,
+    })
+}
+
+/// find every `#[dg(forward = ...)]` and `#[dg(backward = ...)]` attribute
+fn find_dg_directions(attributes: &[Attribute]) -> Vec<&DgDirection> {
+    attributes
+        .iter()
+        .filter_map(|attr| match &attr.body.content {
+            AttributeInner::Dg(attr) => Some(attr.inner.content.iter()),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|inner| match &inner.value {
+            DgInner::Direction(dir) => Some(dir),
+            _ => None,
+        })
+        .collect()
+}
+
+/// find the `#[dg(generate = ...)]` item and the type it declares
+fn find_dg_generate(attributes: &[Attribute]) -> Option<(&DgInner, &DgType)> {
+    dg_inners(attributes).find_map(|inner| match inner {
+        DgInner::Direction(DgDirection::Generate { path, .. }) => Some((inner, path)),
+        _ => None,
+    })
+}
+
+/// find the `#[dg(patch = ...)]` item and the type it declares
+fn find_dg_patch(attributes: &[Attribute]) -> Option<(&DgInner, &DgType)> {
+    dg_inners(attributes).find_map(|inner| match inner {
+        DgInner::Patch(patch) => Some((inner, &patch.path)),
+        _ => None,
+    })
+}
+
+/// the `#[doc = "..."]` attributes, which include `///` comments
+fn doc_attributes(attributes: &[Attribute]) -> impl Iterator<Item = TokenStream> {
+    use doppleganger_macros_parse::ToTokens;
+    use quote::quote;
+
+    attributes
+        .iter()
+        .filter_map(|attr| match &attr.body.content {
+            AttributeInner::Doc(doc) => {
+                let value = doc.value.to_token_stream();
+                Some(quote! { #[doc = #value] })
+            }
+            _ => None,
+        })
+}
+
+/// determine if an optional `for = ...` restriction includes `target`
+fn applies_to(restriction: Option<&ParenthesisGroupContaining<DgFor>>, target: &DgType) -> bool {
+    use doppleganger_macros_parse::ToTokens;
+
+    restriction.is_none_or(|r| {
+        r.content.path.to_token_stream().to_string() == target.to_token_stream().to_string()
+    })
+}
+
+/// Turn a type like `Address<T>` into a path usable in expressions, `Address::<T>`
+fn expr_path(typ: &TokenStream) -> TokenStream {
+    use quote::quote;
+
+    let mut tokens = typ.clone().into_iter();
+    let mut path = TokenStream::new();
+    while let Some(token) = tokens.next() {
+        if let TokenTree::Punct(punct) = &token
+            && punct.as_char() == '<'
+        {
+            path.extend(quote! { :: });
+            path.extend([token]);
+            path.extend(tokens);
+            break;
+        }
+        path.extend([token]);
+    }
+    path
+}
+
+/// generic params for the impl header, without their defaults, and the generic names for
+/// the self type
+fn generics_ts(generics: Option<&GenericParams>) -> (TokenStream, TokenStream) {
+    use quote::quote;
+
+    let Some(generics) = generics else {
+        return (quote! {}, quote! {});
+    };
+
+    let (params_ts, names_ts): (Vec<TokenStream>, Vec<TokenStream>) = generics
+        .params
+        .iter()
+        .map(|p| generic_param_ts(&p.value))
+        .unzip();
+
+    (
+        quote! { < #(#params_ts),* > },
+        quote! { < #(#names_ts),* > },
+    )
+}
+
+/// a generic param for an impl header, without its default, and its name
+fn generic_param_ts(param: &doppleganger_macros_parse::GenericParam) -> (TokenStream, TokenStream) {
+    use doppleganger_macros_parse::{GenericParam, ToTokens};
+    use quote::quote;
+
+    match param {
+        GenericParam::Lifetime { name, bounds } => {
+            let (name, bounds) = (name.to_token_stream(), bounds.to_token_stream());
+            (quote! { #name #bounds }, name)
+        }
+        GenericParam::Type { name, bounds, .. } => {
+            let bounds = bounds.to_token_stream();
+            (quote! { #name #bounds }, name.to_token_stream())
+        }
+        GenericParam::Const { name, typ, .. } => {
+            let typ = typ.to_token_stream();
+            (quote! { const #name: #typ }, name.to_token_stream())
+        }
+    }
+}
+
+/// The where clause of a generated impl: the declared predicates, followed by either the
+/// parsed `#[dg(bound = "...")]` predicates or `Mirror` bounds for every type parameter used in
+/// `mirrored_types`. Bounds are only inferred for `Mirror` and `TryMirror` impls,
+/// `MirrorFrom` impls of several targets need an explicit bound.
+fn where_clause_ts<'t>(
+    clauses: Option<&WhereClauses>,
+    generics: Option<&GenericParams>,
+    bound: Option<&TokenStream>,
+    pass: &Pass,
+    mirrored_types: impl IntoIterator<Item = &'t TokenStream>,
+) -> TokenStream {
+    use doppleganger_macros_parse::ToTokens;
+    use quote::quote;
+
+    let mut predicates: Vec<TokenStream> = clauses
+        .into_iter()
+        .flat_map(|c| c.clauses.iter())
+        .map(|c| c.value.to_token_stream())
+        .collect();
+
+    if let Some(bound) = bound {
+        if !bound.is_empty() {
+            predicates.push(bound.clone());
+        }
+    } else if !pass.targeted {
+        let mirrored_types: Vec<_> = mirrored_types.into_iter().collect();
+        let type_params = type_params(generics);
+        let trait_ts = if pass.fallible {
+            quote! { ::doppleganger::TryMirror }
+        } else if pass.by_ref {
+            quote! { ::doppleganger::MirrorRef }
+        } else {
+            quote! { ::doppleganger::Mirror }
+        };
+        let mut generic_types: Vec<&TokenStream> = Vec::new();
+        for name in &type_params {
+            let mut users = mirrored_types
+                .iter()
+                .filter(|t| mentions_ident(t, name))
+                .peekable();
+            if users.peek().is_none() {
+                continue;
+            }
+            let users: Vec<_> = users.collect();
+            predicates.push(if pass.forward {
+                quote! { #name: #trait_ts<Source = #name> }
+            } else {
+                quote! { #name: #trait_ts<Dest = #name> }
+            });
+            // containers such as `Vec<T>` report their elements' errors as a `MirrorError`
+            if pass.fallible && users.iter().any(|t| *name != t.to_string()) {
+                predicates.push(quote! {
+                    <#name as #trait_ts>::Error: ::core::convert::Into<::doppleganger::MirrorError>
+                });
+            }
+            generic_types.extend(users);
+        }
+
+        // the `?` on generic fields needs their error to convert into the mirror's
+        if pass.fallible {
+            let error = pass.error_ty();
+            generic_types.sort_by_key(|t| t.to_string());
+            generic_types.dedup_by_key(|t| t.to_string());
+            predicates.extend(generic_types.into_iter().map(|typ| {
+                quote! {
+                    #error: ::core::convert::From<<#typ as ::doppleganger::TryMirror>::Error>
+                }
+            }));
+        }
+    }
+
+    if predicates.is_empty() {
+        quote! {}
+    } else {
+        quote! { where #(#predicates),* }
+    }
+}
+
+/// the names of the type parameters of the derived type
+fn type_params(generics: Option<&GenericParams>) -> Vec<Ident> {
+    use doppleganger_macros_parse::GenericParam;
+
+    generics
+        .into_iter()
+        .flat_map(|g| g.params.iter())
+        .filter_map(|p| match &p.value {
+            GenericParam::Type { name, .. } => Some(name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// determine if `ident` appears anywhere in `tokens`
+fn mentions_ident(tokens: &TokenStream, ident: &Ident) -> bool {
+    tokens.clone().into_iter().any(|token| match token {
+        TokenTree::Ident(i) => &i == ident,
+        TokenTree::Group(g) => mentions_ident(&g.stream(), ident),
+        _ => false,
+    })
+}
+
+/// parse the container `#[dg(bound = "...")]` predicates if present
+fn parse_dg_bound(attributes: &[Attribute], errors: &mut Errors) -> Option<TokenStream> {
+    let (inner, bound) = dg_inners(attributes).find_map(|inner| match inner {
+        DgInner::Bound(bound) => Some((inner, bound)),
+        _ => None,
+    })?;
+    match bound.value.as_str().parse() {
+        Ok(bound) => Some(bound),
+        Err(err) => {
+            errors.push(
+                inner.span(),
+                format!("Could not parse #[dg(bound = ...)]: {err}"),
+            );
+            None
+        }
+    }
+}
+
+/// determine if a field should be ignored for `target`
+fn field_has_dg_ignore(attributes: &[Attribute], target: &DgType) -> bool {
+    attributes.iter().any(|attr| match &attr.body.content {
+        AttributeInner::Dg(attr) => attr.inner.content.iter().any(|inner| match &inner.value {
+            DgInner::Ignore(ignore) => applies_to(ignore.target.as_ref(), target),
+            _ => false,
+        }),
+        _ => false,
+    })
+}
+
+/// determine if a field takes no part in mirroring to or from `target`, either because it
+/// is ignored, skipped in the direction of the mirror or because the other type does not
+/// have it and it is filled from a default or computed
+fn field_is_skipped(attributes: &[Attribute], target: &DgType, forward: bool) -> bool {
+    // a defaulted field marked `skip_backward` is still written forward
+    let written_forward =
+        forward && dg_inners(attributes).any(|inner| matches!(inner, DgInner::SkipBackward(_)));
+    field_has_dg_ignore(attributes, target)
+        || (field_get_dg_default(attributes).is_some() && !written_forward)
+        || field_get_dg_compute(attributes).is_some()
+        || dg_inners(attributes).any(|inner| match inner {
+            DgInner::SkipForward(_) => forward,
+            DgInner::SkipBackward(_) => !forward,
+            _ => false,
+        })
+}
+
+/// get the `#[dg(compute = ...)]` expression of a field
+fn field_get_dg_compute(attributes: &[Attribute]) -> Option<&VerbatimUntil<Comma>> {
+    dg_inners(attributes).find_map(|inner| match inner {
+        DgInner::Compute(compute) => Some(&compute.expr),
+        _ => None,
+    })
+}
+
+/// get the path of `#[dg(to = "a.b")]` when mirroring forward, or `#[dg(from = "a.b")]`
+/// when mirroring backward, one ident per segment
+fn field_get_dg_nested(attributes: &[Attribute], forward: bool) -> Option<Vec<Ident>> {
+    use doppleganger_macros_parse::ToTokens;
+    use quote::format_ident;
+
+    dg_inners(attributes)
+        .find_map(|inner| match inner {
+            DgInner::To(to) if forward => Some(to.value.to_token_stream()),
+            DgInner::From(from) if !forward => Some(from.value.to_token_stream()),
+            _ => None,
+        })
+        .map(|value| {
+            value
+                .to_string()
+                .trim_matches('"')
+                .split('.')
+                .map(|segment| format_ident!("{}", segment))
+                .collect()
+        })
+}
+
+/// the span of the `#[dg(to = ...)]` of a field
+fn field_get_dg_to_span(attributes: &[Attribute]) -> Span {
+    dg_inners(attributes)
+        .find(|inner| matches!(inner, DgInner::To(_)))
+        .map_or_else(Span::call_site, |to| to.span())
+}
+
+/// the names and values of the fields that are (`nested`) or are not assigned through a
+/// `#[dg(to = "a.b")]` path
+fn split_nested<'v>(
+    names: &'v [TokenStream],
+    values: &'v [TokenStream],
+    is_nested: &[bool],
+    nested: bool,
+) -> (Vec<&'v TokenStream>, Vec<&'v TokenStream>) {
+    names
+        .iter()
+        .zip(values)
+        .zip(is_nested)
+        .filter(|(_, is_nested)| **is_nested == nested)
+        .map(|(pair, _)| pair)
+        .unzip()
+}
+
+/// get the value a field defaults to when the source does not provide it
+fn field_get_dg_default(attributes: &[Attribute]) -> Option<TokenStream> {
+    use doppleganger_macros_parse::ToTokens;
+    use quote::quote;
+
+    attributes.iter().find_map(|attr| match &attr.body.content {
+        AttributeInner::Dg(attr) => {
+            attr.inner
+                .content
+                .iter()
+                .find_map(|inner| match &inner.value {
+                    DgInner::Default(_) => Some(quote! { ::core::default::Default::default() }),
+                    DgInner::DefaultEquals(default) => Some(default.expr.to_token_stream()),
+                    _ => None,
+                })
+        }
+        _ => None,
+    })
+}
+
+/// get the nested fields of a `#[dg(flatten(...))]` field
+fn field_get_dg_flatten(attributes: &[Attribute]) -> Option<Vec<&Ident>> {
+    attributes.iter().find_map(|attr| match &attr.body.content {
+        AttributeInner::Dg(attr) => {
+            attr.inner
+                .content
+                .iter()
+                .find_map(|inner| match &inner.value {
+                    DgInner::Flatten(flatten) => {
+                        Some(flatten.fields.content.iter().map(|f| &f.value).collect())
+                    }
+                    _ => None,
+                })
+        }
+        _ => None,
+    })
+}
+
+fn field_has_dg_map_back(attributes: &[Attribute]) -> Option<&VerbatimUntil<Comma>> {
+    attributes.iter().find_map(|attr| match &attr.body.content {
+        AttributeInner::Dg(attr) => {
+            attr.inner
+                .content
+                .iter()
+                .find_map(|inner| match &inner.value {
+                    DgInner::MapBack(DgMapBack { expr, .. }) => Some(expr),
+                    _ => None,
+                })
+        }
+        _ => None,
+    })
+}
+
+fn field_has_dg_map(attributes: &[Attribute]) -> Option<&VerbatimUntil<Comma>> {
+    attributes.iter().find_map(|attr| match &attr.body.content {
+        AttributeInner::Dg(attr) => {
+            attr.inner
+                .content
+                .iter()
+                .find_map(|inner| match &inner.value {
+                    DgInner::Map(DgMap { expr, .. }) => Some(expr),
+                    _ => None,
+                })
+        }
+        _ => None,
+    })
+}
+
+/// get the module of a `#[dg(with = ...)]` field, which converts it in both directions
+fn field_get_dg_with(attributes: &[Attribute]) -> Option<&ModPath> {
+    dg_inners(attributes).find_map(|inner| match inner {
+        DgInner::With(with) => Some(&with.path),
+        _ => None,
+    })
+}
+
+/// get the fallible map of a field
+fn field_get_dg_try_map(attributes: &[Attribute]) -> Option<&VerbatimUntil<Comma>> {
+    dg_inners(attributes).find_map(|inner| match inner {
+        DgInner::TryMap(try_map) => Some(&try_map.expr),
+        _ => None,
+    })
+}
+
+/// determine if the output of a map is mirrored as well
+fn field_has_dg_map_then_mirror(attributes: &[Attribute]) -> bool {
+    dg_inners(attributes).any(|inner| matches!(inner, DgInner::MapThenMirror(_)))
+}
+
+/// determine if the field is converted through `AsTryMirror` in a fallible pass
+fn field_has_dg_as_try_mirror(attributes: &[Attribute]) -> bool {
+    dg_inners(attributes).any(|inner| matches!(inner, DgInner::AsTryMirror(_)))
+}
+
+/// determine if the field is converted through `MirrorFrom` in every infallible pass
+fn field_has_dg_mirror_from(attributes: &[Attribute]) -> bool {
+    dg_inners(attributes).any(|inner| matches!(inner, DgInner::MirrorFrom(_)))
+}
+
+/// get the renamed field name for `target` if present, a rename restricted to `target`
+/// takes precedence over an unrestricted one
+fn field_get_dg_rename(attributes: &[Attribute], target: &DgType) -> Option<String> {
+    use doppleganger_macros_parse::ToTokens;
+
+    let renames: Vec<_> = attributes
+        .iter()
+        .filter_map(|attr| match &attr.body.content {
+            AttributeInner::Dg(attr) => Some(attr.inner.content.iter()),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|inner| match &inner.value {
+            DgInner::Rename(rename) if applies_to(rename.target.as_ref(), target) => Some(rename),
+            _ => None,
+        })
+        .collect();
+
+    renames
+        .iter()
+        .find(|rename| rename.target.is_some())
+        .or(renames.first())
+        .map(|rename| {
+            // Extract the string value from the LiteralString
+            let value_str = rename.value.to_token_stream().to_string();
+            // Remove surrounding quotes
+            value_str.trim_matches('"').to_string()
+        })
+}
+
+/// get the tuple position a named field maps to if present
+fn field_get_dg_index(attributes: &[Attribute]) -> Option<usize> {
+    attributes.iter().find_map(|attr| match &attr.body.content {
+        AttributeInner::Dg(attr) => attr.inner.content.iter().find_map(|inner| {
+            if let DgInner::Index(index) = &inner.value {
+                Some(index.value.value() as usize)
+            } else {
+                None
+            }
+        }),
+        _ => None,
+    })
 }
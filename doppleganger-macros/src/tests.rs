@@ -0,0 +1,305 @@
+use super::*;
+use quote::quote;
+
+/// the messages of the `compile_error!`s emitted for `input`, with the line and column each
+/// one points at
+fn compile_errors(input: TokenStream) -> Vec<(String, usize, usize)> {
+    let tokens: Vec<TokenTree> = dg_macros(input).into_iter().collect();
+    tokens
+        .windows(3)
+        .filter_map(|window| match window {
+            [
+                TokenTree::Ident(ident),
+                TokenTree::Punct(bang),
+                TokenTree::Group(group),
+            ] if ident == "compile_error" && bang.as_char() == '!' => {
+                let literal = group.stream().to_string();
+                let message = literal
+                    .strip_prefix('"')
+                    .and_then(|message| message.strip_suffix('"'))
+                    .unwrap_or(&literal)
+                    .replace("\\\"", "\"")
+                    .replace("\\'", "'");
+                let start = ident.span().start();
+                Some((message, start.line, start.column))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn messages(input: TokenStream) -> Vec<String> {
+    compile_errors(input)
+        .into_iter()
+        .map(|(message, _, _)| message)
+        .collect()
+}
+
+#[test]
+fn it_suggests_a_key_for_a_typo() {
+    let errors = messages(quote! {
+        #[dg(forward = Api)]
+        struct User {
+            #[dg(renmae = "username")]
+            name: String,
+        }
+    });
+
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].starts_with("Unknown #[dg(renmae)] attribute, did you mean `rename`? Valid keys are: forward, backward"),
+        "{}",
+        errors[0]
+    );
+}
+
+#[test]
+fn it_reports_the_syntax_of_a_malformed_key() {
+    let errors = messages(quote! {
+        #[dg(forward = Api)]
+        struct User {
+            #[dg(index = "first")]
+            name: String,
+        }
+    });
+
+    assert_eq!(
+        errors,
+        ["Invalid #[dg(index)] attribute, expected `index = 0`"]
+    );
+}
+
+#[test]
+fn it_rejects_duplicate_renames_and_directions() {
+    let errors = messages(quote! {
+        #[dg(forward = Api)]
+        #[dg(forward = Api)]
+        struct User {
+            #[dg(rename = "username", rename = "login")]
+            name: String,
+        }
+    });
+
+    assert_eq!(
+        errors,
+        ["Duplicate #[dg(forward = Api)]", "Duplicate #[dg(rename)]"]
+    );
+}
+
+#[test]
+fn it_rejects_ignore_with_map() {
+    let errors = messages(quote! {
+        #[dg(forward = Api)]
+        struct User {
+            #[dg(ignore, map = str::to_uppercase)]
+            name: String,
+        }
+    });
+
+    assert_eq!(errors, ["#[dg(map)] cannot be combined with #[dg(ignore)]"]);
+}
+
+#[test]
+fn it_rejects_misplaced_keys() {
+    let errors = messages(quote! {
+        #[dg(forward = Api, map = f)]
+        enum Role {
+            #[dg(rename = "Reader")]
+            Viewer(#[dg(flatten(a, b))] Inner),
+        }
+    });
+
+    assert_eq!(
+        errors,
+        [
+            "#[dg(map)] is not supported on enums, only on struct fields or enum variant fields",
+            "#[dg(flatten)] is not supported on enum variant fields, only on struct fields",
+        ]
+    );
+}
+
+#[test]
+fn it_rejects_a_flatten_without_its_fields() {
+    let errors = messages(quote! {
+        #[dg(forward = UserRow)]
+        struct User {
+            #[dg(flatten)]
+            address: Address,
+        }
+    });
+
+    assert_eq!(
+        errors,
+        [
+            "Invalid #[dg(flatten)] attribute, expected `flatten(field, ...)` listing the fields of the nested struct, which the derive cannot see"
+        ]
+    );
+}
+
+#[test]
+fn it_rejects_invalid_renames() {
+    let errors = messages(quote! {
+        #[dg(forward = Api)]
+        struct User {
+            #[dg(rename = "user-name")]
+            name: String,
+        }
+    });
+
+    assert_eq!(
+        errors,
+        ["#[dg(rename = ...)] needs a field or variant name, found \"user-name\""]
+    );
+}
+
+#[test]
+fn it_reports_every_error_of_an_expansion_at_its_item() {
+    let input: TokenStream = r#"#[dg(forward = Api)]
+struct User {
+    #[dg(renmae = "username")]
+    name: String,
+    #[dg(ignore, map = f)]
+    email: String,
+}"#
+    .parse()
+    .expect("Failed to tokenize");
+    let errors: Vec<_> = compile_errors(input)
+        .into_iter()
+        .map(|(message, line, column)| (message.split(',').next().map(str::to_owned), line, column))
+        .collect();
+
+    assert_eq!(
+        errors,
+        [
+            (Some("Unknown #[dg(renmae)] attribute".to_string()), 3, 9),
+            (
+                Some("#[dg(map)] cannot be combined with #[dg(ignore)]".to_string()),
+                5,
+                17
+            ),
+        ]
+    );
+}
+
+#[test]
+fn it_asks_for_a_bound_on_generic_mirror_from_impls() {
+    let errors = messages(quote! {
+        #[dg(both = Dto<T>)]
+        struct Wrapper<T> {
+            values: Vec<T>,
+        }
+    });
+
+    assert_eq!(
+        errors,
+        [
+            "The bounds on `T` cannot be inferred for a type with several targets or a `both`, add them with #[dg(bound = \"...\")]"
+        ]
+    );
+}
+
+#[test]
+fn it_rejects_a_nested_parent_that_is_also_mapped() {
+    let errors = messages(quote! {
+        #[dg(forward = Document)]
+        struct Note {
+            meta: Meta,
+            #[dg(to = "meta.created_at")]
+            created_at: u64,
+        }
+    });
+
+    assert_eq!(
+        errors,
+        [
+            "`meta` is mapped from another field and cannot also be the parent of a #[dg(to = ...)] field"
+        ]
+    );
+}
+
+#[test]
+fn it_rejects_mirror_from_where_it_has_no_effect() {
+    let errors = messages(quote! {
+        #[dg(try_forward = Dto)]
+        struct Team<T> {
+            #[dg(mirror_from)]
+            owner: User,
+        }
+    });
+    assert_eq!(
+        errors,
+        [
+            "#[dg(mirror_from)] only applies to a field converted by a #[dg(forward = ...)], #[dg(backward = ...)] or #[dg(both = ...)] mirror"
+        ]
+    );
+
+    let errors = messages(quote! {
+        #[dg(forward = Dto)]
+        struct Team<T> {
+            #[dg(mirror_from)]
+            users: Vec<T>,
+        }
+    });
+    assert_eq!(
+        errors,
+        [
+            "The bounds on `T` cannot be inferred for a #[dg(mirror_from)] field, add them with #[dg(bound = \"...\")]"
+        ]
+    );
+}
+
+#[test]
+fn it_rejects_as_try_mirror_where_it_has_no_effect() {
+    let errors = messages(quote! {
+        #[dg(forward = Dto)]
+        struct Order {
+            #[dg(as_try_mirror)]
+            total: Money,
+        }
+    });
+    assert_eq!(
+        errors,
+        [
+            "#[dg(as_try_mirror)] only applies to a field converted by a #[dg(try_forward = ...)] or #[dg(try_backward = ...)] mirror"
+        ]
+    );
+
+    let errors = messages(quote! {
+        #[dg(try_forward = Dto)]
+        struct Order<T> {
+            #[dg(as_try_mirror)]
+            total: T,
+        }
+    });
+    assert_eq!(
+        errors,
+        [
+            "The bounds on `T` cannot be inferred for a #[dg(as_try_mirror)] field, add them with #[dg(bound = \"...\")]"
+        ]
+    );
+}
+
+#[test]
+fn it_rejects_a_for_restriction_naming_no_target() {
+    let errors = compile_errors(
+        "#[dg(forward = ApiUser, forward = AuditUser)]
+struct DbUser {
+    #[dg(rename(for = ApiUsr) = \"username\")]
+    name: String,
+    #[dg(ignore(for = AuditUser))]
+    email: String,
+}"
+        .parse()
+        .unwrap(),
+    );
+
+    assert_eq!(
+        errors,
+        [(
+            "#[dg(rename(for = ApiUsr))] names a type this derive does not mirror, expected one of ApiUser, AuditUser"
+                .to_string(),
+            3,
+            22
+        )]
+    );
+}
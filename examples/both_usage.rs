@@ -0,0 +1,108 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom, MirrorInto, MirrorTo};
+
+// Test mirroring in both directions from one declaration
+#[derive(Debug, Clone, PartialEq, Doppleganger)]
+#[dg(both = ProductRow)]
+struct Product {
+    sku: String,
+    #[dg(rename = "price_cents", map = to_cents, map_back = from_cents)]
+    price: f64,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ProductRow {
+    sku: String,
+    price_cents: i64,
+    tags: Vec<String>,
+}
+
+fn to_cents(price: f64) -> i64 {
+    (price * 100.0).round() as i64
+}
+
+fn from_cents(cents: i64) -> f64 {
+    cents as f64 / 100.0
+}
+
+// Two way types can be used as fields of a one way type
+#[derive(Debug, Doppleganger)]
+#[dg(forward = CatalogRow)]
+struct Catalog {
+    name: String,
+    products: Vec<Product>,
+}
+
+#[derive(Debug, PartialEq)]
+struct CatalogRow {
+    name: String,
+    products: Vec<ProductRow>,
+}
+
+// Test both directions on an enum
+#[derive(Debug, Clone, PartialEq, Doppleganger)]
+#[dg(both = WireShape)]
+enum Shape {
+    Circle { radius: u32 },
+    Square(u32),
+}
+
+#[derive(Debug, PartialEq)]
+enum WireShape {
+    Circle { radius: u32 },
+    Square(u32),
+}
+
+fn main() {
+    let product = Product {
+        sku: "W-1".to_string(),
+        price: 19.99,
+        tags: vec!["tools".to_string()],
+    };
+
+    // Test the forward direction
+    let row: ProductRow = product.clone().mirror_to();
+    assert_eq!(
+        row,
+        ProductRow {
+            sku: "W-1".to_string(),
+            price_cents: 1999,
+            tags: vec!["tools".to_string()],
+        }
+    );
+    println!("Forward test passed: {:?}", row);
+
+    // Test the backward direction round trips
+    let back = Product::mirror_from(row);
+    assert_eq!(back, product);
+    println!("Backward test passed: {:?}", back);
+
+    // Test the forward direction is also a `Mirror`, which updates in place
+    let mut row = Product::mirror(product.clone());
+    Product::mirror_into(
+        Product {
+            price: 5.0,
+            ..product.clone()
+        },
+        &mut row,
+    );
+    assert_eq!(row.price_cents, 500);
+    println!("In place test passed: {:?}", row);
+
+    // Test a two way type nested in a one way type
+    let catalog = Catalog::mirror(Catalog {
+        name: "hardware".to_string(),
+        products: vec![product.clone()],
+    });
+    assert_eq!(catalog.products, vec![Product::mirror(product)]);
+    assert_eq!(catalog.name, "hardware");
+    println!("Nested both test passed: {:?}", catalog);
+
+    // Test an enum round trip
+    let wire = WireShape::mirror_from(Shape::Circle { radius: 3 });
+    assert_eq!(wire, WireShape::Circle { radius: 3 });
+    assert_eq!(Shape::mirror_from(WireShape::Square(2)), Shape::Square(2));
+    println!("Enum round trip test passed: {:?}", wire);
+
+    println!("All both direction tests passed!");
+}
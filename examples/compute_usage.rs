@@ -0,0 +1,105 @@
+use doppleganger::{Doppleganger, Mirror, MirrorInto, MirrorRef};
+
+// Test a field computed from the whole source when mirroring backward
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(backward = WirePerson, ref)]
+struct Person {
+    first_name: String,
+    last_name: String,
+    #[dg(compute = full_name)]
+    full_name: String,
+}
+
+struct WirePerson {
+    first_name: String,
+    last_name: String,
+}
+
+fn full_name(person: &WirePerson) -> String {
+    format!("{} {}", person.first_name, person.last_name)
+}
+
+// Test dest fields the source does not have when mirroring forward
+#[derive(Debug, Doppleganger)]
+#[dg(forward = InvoiceView, extra(is_overdue = is_overdue, total = |i: &Invoice| i.lines.iter().sum::<u64>()))]
+struct Invoice {
+    number: u32,
+    due_day: u32,
+    paid: bool,
+    lines: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+struct InvoiceView {
+    number: u32,
+    due_day: u32,
+    paid: bool,
+    lines: Vec<u64>,
+    is_overdue: bool,
+    total: u64,
+}
+
+const TODAY: u32 = 20;
+
+fn is_overdue(invoice: &Invoice) -> bool {
+    !invoice.paid && invoice.due_day < TODAY
+}
+
+fn main() {
+    // Test the computed field reads the source before its fields are moved
+    let person = Person::mirror(WirePerson {
+        first_name: "Ada".to_string(),
+        last_name: "Lovelace".to_string(),
+    });
+    assert_eq!(
+        person,
+        Person {
+            first_name: "Ada".to_string(),
+            last_name: "Lovelace".to_string(),
+            full_name: "Ada Lovelace".to_string(),
+        }
+    );
+    println!("Compute backward test passed: {:?}", person);
+
+    // Test computing from a borrowed source
+    let wire = WirePerson {
+        first_name: "Alan".to_string(),
+        last_name: "Turing".to_string(),
+    };
+    let person = Person::mirror_ref(&wire);
+    assert_eq!(person.full_name, "Alan Turing");
+    assert_eq!(wire.first_name, "Alan");
+    println!("Compute by reference test passed: {:?}", person);
+
+    // Test computing in place overwrites the computed field
+    let mut person = Person {
+        first_name: String::new(),
+        last_name: String::new(),
+        full_name: String::new(),
+    };
+    Person::mirror_into(wire, &mut person);
+    assert_eq!(person.full_name, "Alan Turing");
+    println!("Compute in place test passed: {:?}", person);
+
+    // Test extra fields computed forward
+    let view = Invoice::mirror(Invoice {
+        number: 7,
+        due_day: 12,
+        paid: false,
+        lines: vec![100, 250],
+    });
+    assert_eq!(
+        view,
+        InvoiceView {
+            number: 7,
+            due_day: 12,
+            paid: false,
+            lines: vec![100, 250],
+            is_overdue: true,
+            total: 350,
+        }
+    );
+    println!("Extra forward test passed: {:?}", view);
+
+    println!("All compute tests passed!");
+}
@@ -0,0 +1,105 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom, MirrorTo};
+
+// Test backward mirroring with fields the source does not have
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireUser)]
+struct User {
+    id: u64,
+    name: String,
+    #[dg(default)]
+    login_count: u32,
+    #[dg(default = vec!["user".to_string()])]
+    roles: Vec<String>,
+}
+
+struct WireUser {
+    id: u64,
+    name: String,
+}
+
+// Test defaults on tuple structs keep the positions of the other fields
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireSample)]
+struct Sample(#[dg(default = 1.0)] f64, u32, #[dg(default)] bool, String);
+
+struct WireSample(u32, String);
+
+// Test defaults in both directions, the field is skipped going forward
+#[derive(Debug, Clone, PartialEq, Doppleganger)]
+#[dg(both = CachedRow)]
+struct Cached {
+    key: String,
+    #[dg(default)]
+    hits: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct CachedRow {
+    key: String,
+}
+
+// Test defaults on enum variants
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(backward = WireEvent)]
+enum Event {
+    Login {
+        user: u64,
+        #[dg(default = "web".to_string())]
+        channel: String,
+    },
+    Logout(u64, #[dg(default)] bool),
+}
+
+enum WireEvent {
+    Login { user: u64 },
+    Logout(u64),
+}
+
+fn main() {
+    // Test backward transformation filling defaults
+    let user = User::mirror(WireUser {
+        id: 1,
+        name: "alice".to_string(),
+    });
+    assert_eq!((user.id, user.name.as_str()), (1, "alice"));
+    assert_eq!(user.login_count, 0);
+    assert_eq!(user.roles, ["user"]);
+    println!("Backward default test passed: {:?}", user);
+
+    // Test tuple defaults
+    let sample = Sample::mirror(WireSample(3, "x".to_string()));
+    assert_eq!(sample.0, 1.0);
+    assert_eq!(sample.1, 3);
+    assert!(!sample.2);
+    assert_eq!(sample.3, "x");
+    println!("Tuple default test passed: {:?}", sample);
+
+    // Test defaults when mirroring both ways
+    let row: CachedRow = Cached {
+        key: "k".to_string(),
+        hits: 10,
+    }
+    .mirror_to();
+    assert_eq!(
+        row,
+        CachedRow {
+            key: "k".to_string()
+        }
+    );
+    let cached = Cached::mirror_from(row);
+    assert_eq!(cached.hits, 0);
+    println!("Both default test passed: {:?}", cached);
+
+    // Test enum defaults
+    assert_eq!(
+        Event::mirror(WireEvent::Login { user: 7 }),
+        Event::Login {
+            user: 7,
+            channel: "web".to_string()
+        }
+    );
+    assert_eq!(Event::mirror(WireEvent::Logout(7)), Event::Logout(7, false));
+    println!("Enum default test passed");
+
+    println!("All default tests passed!");
+}
@@ -0,0 +1,81 @@
+use doppleganger::{Doppleganger, Mirror};
+
+// Test forward enum mirroring
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiStatus)]
+enum DbStatus {
+    Pending,
+    #[dg(rename = "Active")]
+    Enabled(u64, #[dg(ignore)] bool),
+    Closed {
+        reason: String,
+        #[dg(rename = "closed_by")]
+        actor: String,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum ApiStatus {
+    Pending,
+    Active(u64),
+    Closed { reason: String, closed_by: String },
+}
+
+// Test backward enum mirroring
+#[derive(Debug, Doppleganger)]
+#[dg(backward = wire::Event)]
+enum Event {
+    Started,
+    Tagged(Vec<String>, Option<u32>),
+    Renamed {
+        #[dg(rename = "old")]
+        from: String,
+        #[dg(rename = "new")]
+        to: String,
+    },
+}
+
+mod wire {
+    #[derive(Debug)]
+    pub enum Event {
+        Started,
+        Tagged(Vec<String>, Option<u32>),
+        Renamed { old: String, new: String },
+    }
+}
+
+fn main() {
+    // Test forward transformation of each variant kind
+    assert_eq!(DbStatus::mirror(DbStatus::Pending), ApiStatus::Pending);
+    let enabled = DbStatus::Enabled(7, true);
+    assert!(matches!(enabled, DbStatus::Enabled(_, true)));
+    assert_eq!(DbStatus::mirror(enabled), ApiStatus::Active(7));
+    let closed = DbStatus::mirror(DbStatus::Closed {
+        reason: "done".to_string(),
+        actor: "alice".to_string(),
+    });
+    assert_eq!(
+        closed,
+        ApiStatus::Closed {
+            reason: "done".to_string(),
+            closed_by: "alice".to_string(),
+        }
+    );
+    println!("Forward enum test passed: {:?}", closed);
+
+    // Test backward transformation of each variant kind
+    assert!(matches!(
+        Event::mirror(wire::Event::Started),
+        Event::Started
+    ));
+    let tagged = Event::mirror(wire::Event::Tagged(vec!["x".to_string()], Some(3)));
+    assert!(matches!(&tagged, Event::Tagged(tags, Some(3)) if tags == &["x"]));
+    let renamed = Event::mirror(wire::Event::Renamed {
+        old: "a".to_string(),
+        new: "b".to_string(),
+    });
+    assert!(matches!(&renamed, Event::Renamed { from, to } if from == "a" && to == "b"));
+    println!("Backward enum test passed: {:?}", renamed);
+
+    println!("All enum tests passed!");
+}
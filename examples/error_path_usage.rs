@@ -0,0 +1,171 @@
+use doppleganger::{Doppleganger, MirrorError, PathSegment, TryMirror};
+use std::{fmt, num::ParseFloatError};
+
+#[derive(Debug, PartialEq)]
+struct InvalidEmail(String);
+
+impl fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid email {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidEmail {}
+
+fn parse_email(email: String) -> Result<String, InvalidEmail> {
+    if email.contains('@') {
+        Ok(email)
+    } else {
+        Err(InvalidEmail(email))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Email(String);
+
+impl TryMirror for Email {
+    type Source = String;
+    type Dest = Email;
+    type Error = InvalidEmail;
+
+    fn try_mirror(source: String) -> Result<Email, InvalidEmail> {
+        parse_email(source).map(Email)
+    }
+}
+
+fn parse_price(price: String) -> Result<f64, ParseFloatError> {
+    price.parse()
+}
+
+// Test the path of a failure deep inside nested containers
+#[derive(Debug, Doppleganger)]
+#[dg(try_backward = WireCart)]
+struct Cart {
+    orders: Vec<Order>,
+}
+
+#[derive(Debug, Doppleganger)]
+#[dg(try_backward = WireOrder)]
+struct Order {
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Doppleganger)]
+#[dg(try_backward = WireItem)]
+struct Item {
+    name: String,
+    #[dg(try_map = parse_price)]
+    price: f64,
+}
+
+struct WireCart {
+    orders: Vec<WireOrder>,
+}
+
+struct WireOrder {
+    items: Vec<WireItem>,
+}
+
+struct WireItem {
+    name: String,
+    price: String,
+}
+
+// Test collecting every field error, with the renamed wire fields in the path
+#[derive(Debug, Doppleganger)]
+#[dg(try_backward = WireSignup, collect_errors)]
+struct Signup {
+    #[dg(rename = "mail", try_map = parse_email)]
+    email: String,
+    #[dg(try_map = |age: String| age.parse::<u8>())]
+    age: u8,
+    backup_emails: Vec<Email>,
+    nickname: Option<String>,
+}
+
+struct WireSignup {
+    mail: String,
+    age: String,
+    backup_emails: Vec<String>,
+    nickname: Option<String>,
+}
+
+fn item(price: &str) -> WireItem {
+    WireItem {
+        name: "pen".to_string(),
+        price: price.to_string(),
+    }
+}
+
+fn main() {
+    // Test the path of a nested failure, the conversion stops at the first one
+    let orders = (0..4)
+        .map(|i| WireOrder {
+            items: if i == 3 {
+                vec![item("free"), item("gratis")]
+            } else {
+                vec![item("2.00")]
+            },
+        })
+        .collect();
+    let err: MirrorError = Cart::try_mirror(WireCart { orders }).unwrap_err();
+    assert_eq!(err.failures().len(), 1);
+    let failure = &err.failures()[0];
+    assert_eq!(failure.location(), "orders[3].items[0].price");
+    assert_eq!(
+        failure.path(),
+        [
+            PathSegment::Field("orders"),
+            PathSegment::Index(3),
+            PathSegment::Field("items"),
+            PathSegment::Index(0),
+            PathSegment::Field("price"),
+        ]
+    );
+    assert!(failure.error().is::<ParseFloatError>());
+    println!("Nested path test passed: {}", err);
+
+    let cart = Cart::try_mirror(WireCart {
+        orders: vec![WireOrder {
+            items: vec![item("1.50")],
+        }],
+    })
+    .unwrap();
+    assert_eq!(cart.orders[0].items[0].price, 1.5);
+    assert_eq!(cart.orders[0].items[0].name, "pen");
+    println!("Nested success test passed");
+
+    // Test collecting every failure, of the fields and of the elements of their containers
+    let err = Signup::try_mirror(WireSignup {
+        mail: "alice".to_string(),
+        age: "old".to_string(),
+        backup_emails: vec!["a@x.io".to_string(), "b".to_string(), "c".to_string()],
+        nickname: None,
+    })
+    .unwrap_err();
+    let locations: Vec<_> = err.failures().iter().map(|f| f.location()).collect();
+    assert_eq!(
+        locations,
+        ["mail", "age", "backup_emails[1]", "backup_emails[2]"]
+    );
+    assert_eq!(
+        err.to_string(),
+        r#"mail: invalid email "alice"; age: invalid digit found in string; backup_emails[1]: invalid email "b"; backup_emails[2]: invalid email "c""#
+    );
+    println!("Collected errors test passed: {}", err);
+
+    let signup = Signup::try_mirror(WireSignup {
+        mail: "alice@x.io".to_string(),
+        age: "31".to_string(),
+        backup_emails: Vec::new(),
+        nickname: Some("al".to_string()),
+    })
+    .unwrap();
+    assert_eq!(signup.email, "alice@x.io");
+    assert_eq!(signup.age, 31);
+    assert!(signup.backup_emails.is_empty());
+    assert_eq!(signup.nickname.as_deref(), Some("al"));
+    println!("Collected success test passed");
+
+    println!("All error path tests passed!");
+}
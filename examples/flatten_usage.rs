@@ -0,0 +1,85 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+// Test forward flattening of a nested struct. The derive cannot see the fields of
+// `Address`, so they are listed, a bare `#[dg(flatten)]` is rejected
+#[derive(Debug, Clone, Doppleganger)]
+#[dg(forward = UserRow)]
+struct User {
+    id: u64,
+    #[dg(flatten(street, city))]
+    address: Address,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserRow {
+    id: u64,
+    street: String,
+    city: String,
+}
+
+// Test rebuilding generic nested structs from flat fields in both directions
+#[derive(Debug, Clone, PartialEq)]
+struct Span<T> {
+    start: T,
+    end: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Doppleganger)]
+#[dg(both = BookingRow)]
+struct Booking {
+    room: String,
+    #[dg(flatten(start, end))]
+    span: Span<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+struct BookingRow {
+    room: String,
+    start: u32,
+    end: u32,
+}
+
+fn main() {
+    // Test forward transformation spreading the nested fields
+    let row = User::mirror(User {
+        id: 1,
+        address: Address {
+            street: "1 Main St".to_string(),
+            city: "Springfield".to_string(),
+        },
+    });
+    assert_eq!(
+        row,
+        UserRow {
+            id: 1,
+            street: "1 Main St".to_string(),
+            city: "Springfield".to_string(),
+        }
+    );
+    println!("Forward flatten test passed: {:?}", row);
+
+    // Test backward transformation rebuilding the nested struct
+    let booking = Booking::mirror_from(BookingRow {
+        room: "A".to_string(),
+        start: 9,
+        end: 17,
+    });
+    assert_eq!(booking.span, Span { start: 9, end: 17 });
+    assert_eq!(
+        BookingRow::mirror_from(booking),
+        BookingRow {
+            room: "A".to_string(),
+            start: 9,
+            end: 17,
+        }
+    );
+    println!("Round trip flatten test passed");
+
+    println!("All flatten tests passed!");
+}
@@ -0,0 +1,72 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom};
+
+// Test declaring the destination from the source, with renames, ignores and docs
+/// A user as stored in the database
+#[derive(Debug, Doppleganger)]
+#[dg(generate = ApiUser, generate_derive(Debug, Clone, PartialEq))]
+pub struct DbUser {
+    /// The unique identifier
+    pub id: u64,
+    #[dg(rename = "username")]
+    pub name: String,
+    pub role: DbRole,
+    pub tags: Vec<String>,
+    #[dg(ignore)]
+    pub password_hash: String,
+}
+
+// Field types are mirrored, so nested derived types use their destination
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiRole)]
+pub enum DbRole {
+    Admin,
+    Member,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiRole {
+    Admin,
+    Member,
+}
+
+// Test generating a tuple struct next to another target
+#[derive(Debug, Doppleganger)]
+#[dg(generate = Point, forward = LegacyPoint, generate_derive(Debug, PartialEq))]
+struct DbPoint(i32, i32, #[dg(ignore(for = Point))] String);
+
+#[derive(Debug, PartialEq)]
+struct LegacyPoint(i32, i32, String);
+
+fn main() {
+    // Test the generated struct and its mirror
+    let user = DbUser {
+        id: 1,
+        name: "alice".to_string(),
+        role: DbRole::Admin,
+        tags: vec!["a".to_string()],
+        password_hash: "hash".to_string(),
+    };
+    assert_eq!(user.password_hash, "hash");
+    let api: ApiUser = DbUser::mirror(user);
+    assert_eq!(
+        api.clone(),
+        ApiUser {
+            id: 1,
+            username: "alice".to_string(),
+            role: ApiRole::Admin,
+            tags: vec!["a".to_string()],
+        }
+    );
+    println!("Generated struct test passed: {:?}", api);
+
+    // Test the generated tuple struct
+    let point = Point::mirror_from(DbPoint(1, 2, "origin".to_string()));
+    assert_eq!(point, Point(1, 2));
+    let legacy = LegacyPoint::mirror_from(DbPoint(3, 4, "legacy".to_string()));
+    assert_eq!(legacy, LegacyPoint(3, 4, "legacy".to_string()));
+    println!("Generated tuple struct test passed: {:?}", point);
+
+    let member = DbRole::mirror(DbRole::Member);
+    assert_eq!(member, ApiRole::Member);
+    println!("All generate tests passed!");
+}
@@ -0,0 +1,170 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom};
+
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiUser)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiUser {
+    id: u64,
+    name: String,
+}
+
+// Test forward mirroring of a generic struct with defaulted params; the
+// `T: Mirror<Source = T>` bound is inferred from the `items` field
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiPage<T::Dest, N>)]
+struct Page<T, const N: usize = 10> {
+    items: Vec<T>,
+    cursor: Option<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiPage<T, const N: usize> {
+    items: Vec<T>,
+    cursor: Option<u32>,
+}
+
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireMember)]
+struct Member {
+    id: u64,
+}
+
+struct WireMember {
+    id: u64,
+}
+
+// Test backward mirroring of a generic struct; the `T: Mirror<Dest = T>`
+// bound is inferred from the `members` field
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireTeam<T::Source>)]
+struct Team<T> {
+    name: String,
+    members: Vec<T>,
+}
+
+struct WireTeam<T> {
+    name: String,
+    members: Vec<T>,
+}
+
+// Test overriding the inferred bounds
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiTagged<T>, bound = "T: ::doppleganger::Mirror<Source = T, Dest = T>")]
+struct Tagged<T> {
+    tag: String,
+    value: T,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiTagged<T> {
+    tag: String,
+    value: T,
+}
+
+// Test a generic type mirrored both ways, whose `MirrorFrom` half needs an explicit bound
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(both = RowBatch<T>, bound = "T: ::doppleganger::Primitive")]
+struct Batch<T> {
+    rows: Vec<T>,
+}
+
+#[derive(Debug, PartialEq)]
+struct RowBatch<T> {
+    rows: Vec<T>,
+}
+
+// Test generic enums
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiOutcome<T::Dest>)]
+enum Outcome<T> {
+    Found(T),
+    Missing { reason: String },
+}
+
+#[derive(Debug, PartialEq)]
+enum ApiOutcome<T> {
+    Found(T),
+    Missing { reason: String },
+}
+
+fn main() {
+    // Test forward transformation of a generic struct
+    let page: Page<User> = Page {
+        items: vec![User {
+            id: 1,
+            name: "Alice".to_string(),
+        }],
+        cursor: Some(2),
+    };
+    let api_page = Page::mirror(page);
+    assert_eq!(
+        api_page,
+        ApiPage::<ApiUser, 10> {
+            items: vec![ApiUser {
+                id: 1,
+                name: "Alice".to_string()
+            }],
+            cursor: Some(2),
+        }
+    );
+    println!("Forward generic test passed: {:?}", api_page);
+
+    // Test backward transformation of a generic struct
+    let team: Team<Member> = Team::mirror(WireTeam {
+        name: "core".to_string(),
+        members: vec![WireMember { id: 2 }],
+    });
+    assert_eq!(team.name, "core");
+    assert_eq!(team.members[0].id, 2);
+    println!("Backward generic test passed: {:?}", team);
+
+    // Test the bound override
+    let tagged = Tagged::mirror(Tagged {
+        tag: "answer".to_string(),
+        value: 42u32,
+    });
+    assert_eq!(
+        tagged,
+        ApiTagged {
+            tag: "answer".to_string(),
+            value: 42
+        }
+    );
+    println!("Bound override test passed: {:?}", tagged);
+
+    // Test generic enums
+    let found = Outcome::mirror(Outcome::Found(User {
+        id: 3,
+        name: "Carol".to_string(),
+    }));
+    assert_eq!(
+        found,
+        ApiOutcome::Found(ApiUser {
+            id: 3,
+            name: "Carol".to_string()
+        })
+    );
+    let missing = Outcome::<User>::mirror(Outcome::Missing {
+        reason: "gone".to_string(),
+    });
+    assert_eq!(
+        missing,
+        ApiOutcome::Missing {
+            reason: "gone".to_string()
+        }
+    );
+    println!("Generic enum test passed");
+
+    // Test a round trip of a generic `both`
+    let rows = Batch::mirror(Batch { rows: vec![1u8, 2] });
+    assert_eq!(rows, RowBatch { rows: vec![1, 2] });
+    assert_eq!(Batch::mirror_from(rows), Batch { rows: vec![1u8, 2] });
+    println!("Generic both test passed");
+
+    println!("All generic tests passed!");
+}
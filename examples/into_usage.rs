@@ -0,0 +1,228 @@
+use doppleganger::{Doppleganger, Mirror, MirrorInto, MirrorIntoFrom};
+
+// Test updating a long-lived entity from an incoming DTO, keeping its own state
+#[derive(Debug, Doppleganger)]
+#[dg(backward = UserDto)]
+struct UserEntity {
+    id: u64,
+    #[dg(rename = "username")]
+    name: String,
+    roles: Vec<Role>,
+    #[dg(flatten(street, city))]
+    address: Address,
+    #[dg(default)]
+    version: u32,
+    #[dg(default)]
+    dirty: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+struct UserDto {
+    id: u64,
+    username: String,
+    roles: Vec<RoleDto>,
+    street: String,
+    city: String,
+}
+
+// Test enums, which are replaced as a whole
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(backward = RoleDto)]
+enum Role {
+    Admin,
+    Member { team: String },
+}
+
+enum RoleDto {
+    Admin,
+    Member { team: String },
+}
+
+// Test the forward direction on tuple structs with an ignored field
+#[derive(Debug, Doppleganger)]
+#[dg(forward = Row)]
+struct Record(u64, #[dg(ignore)] String, String);
+
+#[derive(Debug, PartialEq)]
+struct Row(u64, String);
+
+// Test updating an entity that mirrors both ways, its backward half is a `MirrorFrom`
+#[derive(Debug, Doppleganger)]
+#[dg(both = ProductDto)]
+struct ProductEntity {
+    sku: String,
+    price_cents: u64,
+    #[dg(skip_backward, default)]
+    version: u32,
+}
+
+#[derive(Debug, PartialEq)]
+struct ProductDto {
+    sku: String,
+    price_cents: u64,
+    version: u32,
+}
+
+// Test updating a type with several targets from each of them, a built account takes the
+// fields its source does not have from its `Default`
+#[derive(Debug, Default, Doppleganger)]
+#[dg(backward = CreateAccount)]
+#[dg(backward = RenameAccount)]
+#[dg(default)]
+struct Account {
+    #[dg(ignore(for = RenameAccount))]
+    email: String,
+    name: String,
+    #[dg(ignore)]
+    logins: u32,
+}
+
+struct CreateAccount {
+    email: String,
+    name: String,
+}
+
+struct RenameAccount {
+    name: String,
+}
+
+fn main() {
+    // Test the in place backward mirror
+    let mut entity = UserEntity {
+        id: 1,
+        name: "alice".to_string(),
+        roles: vec![Role::Admin],
+        address: Address {
+            street: "1 Main St".to_string(),
+            city: "Paris".to_string(),
+        },
+        version: 7,
+        dirty: true,
+    };
+    UserEntity::mirror_into(
+        UserDto {
+            id: 1,
+            username: "alice2".to_string(),
+            roles: vec![RoleDto::Member {
+                team: "core".to_string(),
+            }],
+            street: "2 Main St".to_string(),
+            city: "Lyon".to_string(),
+        },
+        &mut entity,
+    );
+    assert_eq!(entity.name, "alice2");
+    assert_eq!(
+        entity.roles,
+        [Role::Member {
+            team: "core".to_string()
+        }]
+    );
+    assert_eq!(
+        entity.address,
+        Address {
+            street: "2 Main St".to_string(),
+            city: "Lyon".to_string(),
+        }
+    );
+    assert_eq!(entity.version, 7);
+    assert!(entity.dirty);
+    println!("In place backward test passed: {:?}", entity);
+
+    // Test the in place enum mirror
+    let mut role = Role::Member {
+        team: "core".to_string(),
+    };
+    Role::mirror_into(RoleDto::Admin, &mut role);
+    assert_eq!(role, Role::Admin);
+    Role::mirror_into(
+        RoleDto::Member {
+            team: "ops".to_string(),
+        },
+        &mut role,
+    );
+    assert_eq!(
+        role,
+        Role::Member {
+            team: "ops".to_string()
+        }
+    );
+    println!("In place enum test passed: {:?}", role);
+
+    // Test the in place forward mirror on tuple structs
+    let mut row = Row(0, String::new());
+    let record = Record(3, "secret".to_string(), "b".to_string());
+    assert_eq!(record.1, "secret");
+    Record::mirror_into(record, &mut row);
+    assert_eq!(row, Row(3, "b".to_string()));
+    assert_eq!(
+        Record::mirror(Record(4, String::new(), "c".to_string())),
+        Row(4, "c".to_string())
+    );
+    println!("In place forward test passed: {:?}", row);
+
+    // Test the in place backward half of a `both`
+    let mut product = ProductEntity {
+        sku: "A-1".to_string(),
+        price_cents: 100,
+        version: 4,
+    };
+    ProductEntity::mirror_into_from(
+        ProductDto {
+            sku: "A-1".to_string(),
+            price_cents: 250,
+            version: 0,
+        },
+        &mut product,
+    );
+    assert_eq!(product.price_cents, 250);
+    assert_eq!(product.version, 4);
+    let mut dto = ProductDto {
+        sku: String::new(),
+        price_cents: 0,
+        version: 0,
+    };
+    ProductDto::mirror_into_from(product, &mut dto);
+    assert_eq!(
+        dto,
+        ProductDto {
+            sku: "A-1".to_string(),
+            price_cents: 250,
+            version: 4,
+        }
+    );
+    println!("In place both test passed: {:?}", dto);
+
+    // Test the in place update from each of several targets
+    let mut account = Account {
+        email: "old@x.io".to_string(),
+        name: "old".to_string(),
+        logins: 12,
+    };
+    Account::mirror_into_from(
+        RenameAccount {
+            name: "new".to_string(),
+        },
+        &mut account,
+    );
+    assert_eq!(account.email, "old@x.io");
+    assert_eq!(account.name, "new");
+    Account::mirror_into_from(
+        CreateAccount {
+            email: "new@x.io".to_string(),
+            name: "newer".to_string(),
+        },
+        &mut account,
+    );
+    assert_eq!(account.email, "new@x.io");
+    assert_eq!(account.name, "newer");
+    assert_eq!(account.logins, 12);
+    println!("In place several targets test passed: {:?}", account);
+
+    println!("All MirrorInto tests passed!");
+}
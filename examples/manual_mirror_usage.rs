@@ -0,0 +1,202 @@
+use doppleganger::{Doppleganger, Mirror, MirrorInto, TryMirror};
+
+// A type with a hand written `Mirror`, used as a field of derived types
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Money {
+    units: u64,
+    cents: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cents(u64);
+
+impl Mirror for Money {
+    type Source = Money;
+    type Dest = Cents;
+
+    fn mirror(source: Self::Source) -> Self::Dest {
+        Cents(source.units * 100 + u64::from(source.cents))
+    }
+}
+
+// Test a hand written `Mirror` field, alone and in a container
+#[derive(Debug, Doppleganger)]
+#[dg(forward = OrderDto)]
+struct Order {
+    id: u64,
+    price: Money,
+    discounts: Vec<Money>,
+}
+
+#[derive(Debug, PartialEq)]
+struct OrderDto {
+    id: u64,
+    price: Cents,
+    discounts: Vec<Cents>,
+}
+
+// Test a hand written `Mirror` in an enum payload
+#[derive(Debug, Doppleganger)]
+#[dg(forward = PaymentDto)]
+enum Payment {
+    Card { amount: Money },
+    Cash(Money),
+    Free,
+}
+
+#[derive(Debug, PartialEq)]
+enum PaymentDto {
+    Card { amount: Cents },
+    Cash(Cents),
+    Free,
+}
+
+// Test a hand written `Mirror` field of a flattened struct
+#[derive(Debug, Clone)]
+struct Totals {
+    net: Money,
+    tax: Money,
+}
+
+#[derive(Debug, Doppleganger)]
+#[dg(forward = InvoiceRow)]
+struct Invoice {
+    number: u32,
+    #[dg(flatten(net, tax))]
+    totals: Totals,
+}
+
+#[derive(Debug, PartialEq)]
+struct InvoiceRow {
+    number: u32,
+    net: Cents,
+    tax: Cents,
+}
+
+// Test a hand written `Mirror` field of a generated type
+#[derive(Debug, Doppleganger)]
+#[dg(generate = RefundDto, generate_derive(Debug, PartialEq))]
+struct Refund {
+    amount: Money,
+}
+
+// Test a hand written `Mirror` field of a fallible mirror, which has no `TryMirror` impl
+#[derive(Debug, Doppleganger)]
+#[dg(try_forward = CheckoutDto)]
+struct Checkout {
+    #[dg(as_try_mirror)]
+    total: Money,
+    #[dg(try_map = u32::try_from)]
+    quantity: i64,
+}
+
+#[derive(Debug, PartialEq)]
+struct CheckoutDto {
+    total: Cents,
+    quantity: u32,
+}
+
+fn main() {
+    let price = Money {
+        units: 12,
+        cents: 50,
+    };
+
+    // Test the field and the container use the hand written impl
+    let order = Order::mirror(Order {
+        id: 7,
+        price,
+        discounts: vec![Money { units: 1, cents: 0 }],
+    });
+    assert_eq!(
+        order,
+        OrderDto {
+            id: 7,
+            price: Cents(1250),
+            discounts: vec![Cents(100)],
+        }
+    );
+    println!("Struct field test passed: {:?}", order);
+
+    // Test in place
+    let mut order = order;
+    Order::mirror_into(
+        Order {
+            id: 8,
+            price: Money { units: 2, cents: 5 },
+            discounts: Vec::new(),
+        },
+        &mut order,
+    );
+    assert_eq!(order.price, Cents(205));
+    println!("In place test passed: {:?}", order);
+
+    // Test enum payloads
+    assert_eq!(
+        Payment::mirror(Payment::Card { amount: price }),
+        PaymentDto::Card {
+            amount: Cents(1250)
+        }
+    );
+    assert_eq!(
+        Payment::mirror(Payment::Cash(price)),
+        PaymentDto::Cash(Cents(1250))
+    );
+    assert_eq!(Payment::mirror(Payment::Free), PaymentDto::Free);
+    println!("Enum payload test passed");
+
+    // Test flattened fields
+    let row = Invoice::mirror(Invoice {
+        number: 3,
+        totals: Totals {
+            net: price,
+            tax: Money {
+                units: 2,
+                cents: 50,
+            },
+        },
+    });
+    assert_eq!(
+        row,
+        InvoiceRow {
+            number: 3,
+            net: Cents(1250),
+            tax: Cents(250),
+        }
+    );
+    println!("Flatten test passed: {:?}", row);
+
+    // Test generated fields
+    let refund = Refund::mirror(Refund { amount: price });
+    assert_eq!(
+        refund,
+        RefundDto {
+            amount: Cents(1250)
+        }
+    );
+    println!("Generate test passed: {:?}", refund);
+
+    // Test a fallible mirror converts the field with the hand written impl
+    let checkout = Checkout::try_mirror(Checkout {
+        total: price,
+        quantity: 2,
+    })
+    .unwrap();
+    assert_eq!(
+        checkout,
+        CheckoutDto {
+            total: Cents(1250),
+            quantity: 2,
+        }
+    );
+    assert!(
+        Checkout::try_mirror(Checkout {
+            total: price,
+            quantity: -1,
+        })
+        .is_err()
+    );
+    println!("Fallible mirror test passed: {:?}", checkout);
+
+    println!("All hand written Mirror tests passed!");
+}
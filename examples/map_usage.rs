@@ -0,0 +1,173 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom};
+use std::sync::Arc;
+
+// Test backward maps written as closures, turbofish paths and method references
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireProfile<'static>)]
+struct Profile {
+    #[dg(map = |s: String| s.trim().to_owned())]
+    display_name: String,
+    #[dg(map = Into::<Arc<str>>::into)]
+    handle: Arc<str>,
+    #[dg(rename = "bio", map = str::len)]
+    bio_len: usize,
+}
+
+struct WireProfile<'a> {
+    display_name: String,
+    handle: String,
+    bio: &'a str,
+}
+
+// Test forward maps on generic and container fields, the map output is used as is
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiBatch)]
+struct Batch<T> {
+    #[dg(rename = "count", map = |items: Vec<T>| items.len())]
+    items: Vec<T>,
+    #[dg(map = Option::unwrap_or_default)]
+    tags: Option<Vec<String>>,
+    label: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiBatch {
+    count: usize,
+    tags: Vec<String>,
+    label: String,
+}
+
+// Test backward maps on generic and container fields
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireWrapped<T>)]
+struct Wrapped<T> {
+    #[dg(map = Some)]
+    value: Option<T>,
+    #[dg(map = |ids: Vec<u64>| ids.into_iter().map(UserId).collect())]
+    ids: Vec<UserId>,
+}
+
+struct WireWrapped<T> {
+    value: T,
+    ids: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserId(u64);
+
+// Test mapping and then mirroring the output
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiUser)]
+struct User {
+    name: String,
+    active: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiUser {
+    name: String,
+    active: bool,
+}
+
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiTeam)]
+struct Team {
+    #[dg(
+        map = |users: Vec<User>| users.into_iter().filter(|u| u.active).collect(),
+        map_then_mirror
+    )]
+    users: Vec<User>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiTeam {
+    users: Vec<ApiUser>,
+}
+
+// Test closures in a map/map_back pair
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(both = DbPrice)]
+struct Price {
+    #[dg(
+        rename = "cents",
+        map = |amount: f64| (amount * 100.0).round() as i64,
+        map_back = |cents: i64| cents as f64 / 100.0
+    )]
+    amount: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct DbPrice {
+    cents: i64,
+}
+
+fn main() {
+    // Test backward transformation with expression maps
+    let profile = Profile::mirror(WireProfile {
+        display_name: "  Alice  ".to_string(),
+        handle: "alice".to_string(),
+        bio: "Writes Rust",
+    });
+    assert_eq!(profile.display_name, "Alice");
+    assert_eq!(&*profile.handle, "alice");
+    assert_eq!(profile.bio_len, 11);
+    println!("Expression map test passed: {:?}", profile);
+
+    // Test forward maps on generic and container fields
+    let batch = Batch::mirror(Batch {
+        items: vec!['a', 'b'],
+        tags: None,
+        label: "letters".to_string(),
+    });
+    assert_eq!(
+        batch,
+        ApiBatch {
+            count: 2,
+            tags: Vec::new(),
+            label: "letters".to_string(),
+        }
+    );
+    println!("Forward map test passed: {:?}", batch);
+
+    // Test backward maps on generic and container fields
+    let wrapped: Wrapped<u8> = Wrapped::mirror(WireWrapped {
+        value: 7,
+        ids: vec![1, 2],
+    });
+    assert_eq!(wrapped.value, Some(7));
+    assert_eq!(wrapped.ids, [UserId(1), UserId(2)]);
+    println!("Backward map test passed: {:?}", wrapped);
+
+    // Test mapping and then mirroring
+    let team = Team::mirror(Team {
+        users: vec![
+            User {
+                name: "Alice".to_string(),
+                active: true,
+            },
+            User {
+                name: "Bob".to_string(),
+                active: false,
+            },
+        ],
+    });
+    assert_eq!(
+        team,
+        ApiTeam {
+            users: vec![ApiUser {
+                name: "Alice".to_string(),
+                active: true,
+            }],
+        }
+    );
+    println!("Map then mirror test passed: {:?}", team);
+
+    // Test closures in both directions
+    let db_price = DbPrice::mirror_from(Price { amount: 12.34 });
+    assert_eq!(db_price, DbPrice { cents: 1234 });
+    let price = Price::mirror_from(db_price);
+    assert_eq!(price, Price { amount: 12.34 });
+    println!("Closure map pair test passed: {:?}", price);
+
+    println!("All map expression tests passed!");
+}
@@ -0,0 +1,141 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom, MirrorTo};
+
+// Test a type with several forward targets
+#[derive(Debug, Clone, Doppleganger)]
+#[dg(forward = ApiUser)]
+#[dg(forward = AuditUser)]
+struct DbUser {
+    id: u64,
+    #[dg(rename(for = ApiUser) = "username")]
+    name: String,
+    #[dg(ignore(for = AuditUser))]
+    addresses: Vec<DbAddress>,
+    #[dg(ignore)]
+    password_hash: String,
+}
+
+// Single target types can still be used as fields of a multi target type
+#[derive(Debug, Clone, Doppleganger)]
+#[dg(forward = ApiAddress)]
+struct DbAddress {
+    city: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiAddress {
+    city: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiUser {
+    id: u64,
+    username: String,
+    addresses: Vec<ApiAddress>,
+}
+
+#[derive(Debug, PartialEq)]
+struct AuditUser {
+    id: u64,
+    name: String,
+}
+
+// Multi target types only implement `MirrorFrom`, so the fields of a single target type
+// holding them convert through it with #[dg(mirror_from)]
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiTeam)]
+struct DbTeam {
+    name: String,
+    #[dg(mirror_from)]
+    users: Vec<DbUser>,
+    #[dg(mirror_from)]
+    owner: Option<DbUser>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiTeam {
+    name: String,
+    users: Vec<ApiUser>,
+    owner: Option<AuditUser>,
+}
+
+// Test an enum with both a forward and a backward target
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(forward = ApiRole)]
+#[dg(backward = WireRole)]
+enum Role {
+    Admin,
+    #[dg(rename(for = WireRole) = "Reader")]
+    Viewer,
+}
+
+#[derive(Debug, PartialEq)]
+enum ApiRole {
+    Admin,
+    Viewer,
+}
+
+enum WireRole {
+    Admin,
+    Reader,
+}
+
+fn main() {
+    let db_user = DbUser {
+        id: 1,
+        name: "alice".to_string(),
+        addresses: vec![DbAddress {
+            city: "Paris".to_string(),
+        }],
+        password_hash: "hunter2".to_string(),
+    };
+    assert!(!db_user.password_hash.is_empty());
+
+    // Test the API projection
+    let api_user = ApiUser::mirror_from(db_user.clone());
+    assert_eq!(
+        api_user,
+        ApiUser {
+            id: 1,
+            username: "alice".to_string(),
+            addresses: vec![ApiAddress {
+                city: "Paris".to_string()
+            }],
+        }
+    );
+    println!("API projection test passed: {:?}", api_user);
+
+    // Test the audit projection
+    let audit_user: AuditUser = db_user.clone().mirror_to();
+    assert_eq!(
+        audit_user,
+        AuditUser {
+            id: 1,
+            name: "alice".to_string(),
+        }
+    );
+    println!("Audit projection test passed: {:?}", audit_user);
+
+    // Test each field picks the projection of its destination type
+    let team = DbTeam::mirror(DbTeam {
+        name: "core".to_string(),
+        users: vec![db_user.clone()],
+        owner: Some(db_user),
+    });
+    assert_eq!(
+        team,
+        ApiTeam {
+            name: "core".to_string(),
+            users: vec![api_user],
+            owner: Some(audit_user),
+        }
+    );
+    println!("Nested multi target test passed: {:?}", team);
+
+    // Test both directions of an enum
+    assert_eq!(ApiRole::mirror_from(Role::Viewer), ApiRole::Viewer);
+    assert_eq!(Role::mirror_from(WireRole::Reader), Role::Viewer);
+    assert_eq!(Role::mirror_from(WireRole::Admin), Role::Admin);
+    println!("Enum targets test passed");
+
+    println!("All multi target tests passed!");
+}
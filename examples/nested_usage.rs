@@ -0,0 +1,172 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom, MirrorInto, TryMirror};
+
+// Test reading single fields out of a nested source
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(backward = WireCustomer)]
+struct Customer {
+    name: String,
+    #[dg(from = "address.city")]
+    city: String,
+    #[dg(from = "address.geo.country")]
+    country: String,
+}
+
+struct WireCustomer {
+    name: String,
+    address: WireAddress,
+}
+
+struct WireAddress {
+    city: String,
+    geo: WireGeo,
+}
+
+struct WireGeo {
+    country: String,
+}
+
+// Test writing fields into a nested destination. `Meta` is built from its `Default` and then
+// filled by the fields that target it, `revision` keeps its default
+#[derive(Debug, Doppleganger)]
+#[dg(forward = Document)]
+struct Note {
+    title: String,
+    #[dg(to = "meta.created_at")]
+    created_at: u64,
+    #[dg(to = "meta.author")]
+    author: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Document {
+    title: String,
+    meta: Meta,
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct Meta {
+    created_at: u64,
+    author: String,
+    revision: u32,
+}
+
+// Test the same nested field in both directions
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(both = Document)]
+struct Draft {
+    title: String,
+    #[dg(from = "meta.revision", to = "meta.revision")]
+    revision: u32,
+}
+
+// Test nested paths in the error of a fallible conversion
+#[derive(Debug, Doppleganger)]
+#[dg(try_backward = WireOrder, collect_errors)]
+struct Order {
+    #[dg(from = "shipping.zip", try_map = |zip: String| zip.parse::<u32>())]
+    zip: u32,
+    #[dg(from = "shipping.country")]
+    country: String,
+}
+
+struct WireOrder {
+    shipping: WireShipping,
+}
+
+struct WireShipping {
+    zip: String,
+    country: String,
+}
+
+fn main() {
+    // Test the nested source fields are read
+    let customer = Customer::mirror(WireCustomer {
+        name: "Ada".to_string(),
+        address: WireAddress {
+            city: "London".to_string(),
+            geo: WireGeo {
+                country: "UK".to_string(),
+            },
+        },
+    });
+    assert_eq!(
+        customer,
+        Customer {
+            name: "Ada".to_string(),
+            city: "London".to_string(),
+            country: "UK".to_string(),
+        }
+    );
+    println!("Nested from test passed: {:?}", customer);
+
+    // Test the nested destination is built from its targeted fields and its default
+    let document = Note::mirror(Note {
+        title: "Notes".to_string(),
+        created_at: 1_700_000_000,
+        author: "ada".to_string(),
+    });
+    assert_eq!(
+        document,
+        Document {
+            title: "Notes".to_string(),
+            meta: Meta {
+                created_at: 1_700_000_000,
+                author: "ada".to_string(),
+                revision: 0,
+            },
+        }
+    );
+    println!("Nested to test passed: {:?}", document);
+
+    // Test in place only the targeted nested fields are overwritten
+    let mut document = document;
+    document.meta.revision = 3;
+    Note::mirror_into(
+        Note {
+            title: "Edited".to_string(),
+            created_at: 1_800_000_000,
+            author: "alan".to_string(),
+        },
+        &mut document,
+    );
+    assert_eq!(document.title, "Edited");
+    assert_eq!(document.meta.created_at, 1_800_000_000);
+    assert_eq!(document.meta.revision, 3);
+    println!("Nested in place test passed: {:?}", document);
+
+    // Test both directions
+    let draft = Draft::mirror_from(document);
+    assert_eq!(
+        draft,
+        Draft {
+            title: "Edited".to_string(),
+            revision: 3,
+        }
+    );
+    let document = Document::mirror_from(draft);
+    assert_eq!(document.meta.revision, 3);
+    assert_eq!(document.meta.created_at, 0);
+    println!("Nested both test passed: {:?}", document);
+
+    // Test a failure records the nested path
+    let order = Order::try_mirror(WireOrder {
+        shipping: WireShipping {
+            zip: "12345".to_string(),
+            country: "US".to_string(),
+        },
+    })
+    .unwrap();
+    assert_eq!(order.zip, 12345);
+    assert_eq!(order.country, "US");
+    let err = Order::try_mirror(WireOrder {
+        shipping: WireShipping {
+            zip: "abc".to_string(),
+            country: "US".to_string(),
+        },
+    })
+    .unwrap_err();
+    assert_eq!(err.failures()[0].location(), "shipping.zip");
+    println!("Nested error path test passed: {}", err);
+
+    println!("All nested tests passed!");
+}
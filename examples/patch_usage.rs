@@ -0,0 +1,105 @@
+use doppleganger::{Doppleganger, Mirror};
+
+// Test a patch type next to a mirror, with renames and ignores
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiUser, patch = UserPatch, patch_derive(Debug, Default))]
+pub struct User {
+    pub id: u64,
+    /// The display name
+    #[dg(rename = "username")]
+    pub name: String,
+    pub nickname: Option<String>,
+    pub tags: Vec<String>,
+    #[dg(ignore(for = UserPatch))]
+    pub version: u32,
+}
+
+pub struct ApiUser {
+    pub id: u64,
+    pub username: String,
+    pub nickname: Option<String>,
+    pub tags: Vec<String>,
+    pub version: u32,
+}
+
+// Test a patch of a generic tuple struct without any mirror
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(patch = PairPatch, patch_derive(Default))]
+struct Pair<T>(T, #[dg(ignore)] u8, T);
+
+// Test a patch that leaves out the only field using a type parameter
+#[derive(Debug, Doppleganger)]
+#[dg(patch = CachedPatch, patch_derive(Default))]
+struct Cached<'a, T>
+where
+    T: Clone,
+{
+    key: &'a str,
+    hits: u32,
+    #[dg(ignore)]
+    value: T,
+}
+
+fn user() -> User {
+    User {
+        id: 1,
+        name: "alice".to_string(),
+        nickname: Some("al".to_string()),
+        tags: vec!["a".to_string()],
+        version: 3,
+    }
+}
+
+fn main() {
+    // Test only the present fields are written
+    let mut target = user();
+    UserPatch {
+        username: Some("alice2".to_string()),
+        ..Default::default()
+    }
+    .apply(&mut target);
+    assert_eq!(target.name, "alice2");
+    assert_eq!(target.nickname.as_deref(), Some("al"));
+    assert_eq!(target.tags, ["a"]);
+    assert_eq!(target.version, 3);
+    println!("Patch test passed: {:?}", target);
+
+    // Test the nested option, `Some(None)` clears an optional field
+    let patch = UserPatch {
+        id: Some(2),
+        nickname: Some(None),
+        tags: Some(Vec::new()),
+        ..Default::default()
+    };
+    println!("Applying {:?}", patch);
+    patch.apply(&mut target);
+    assert_eq!(target.id, 2);
+    assert_eq!(target.nickname, None);
+    assert!(target.tags.is_empty());
+    println!("Clearing patch test passed: {:?}", target);
+    assert_eq!(User::mirror(target).username, "alice2");
+
+    // Test the patch of a generic tuple struct
+    let mut pair = Pair(1, 0, 2);
+    PairPatch(None, Some(5)).apply(&mut pair);
+    assert_eq!(pair, Pair(1, 0, 5));
+    PairPatch::default().apply(&mut pair);
+    assert_eq!(pair, Pair(1, 0, 5));
+    println!("Generic tuple patch test passed: {:?}", pair);
+
+    // Test the unused parameter is only declared on `apply`
+    let mut cached = Cached {
+        key: "a",
+        hits: 1,
+        value: vec![1],
+    };
+    CachedPatch {
+        key: Some("b"),
+        ..Default::default()
+    }
+    .apply(&mut cached);
+    assert_eq!((cached.key, cached.hits, cached.value), ("b", 1, vec![1]));
+    println!("Unused parameter patch test passed");
+
+    println!("All patch tests passed!");
+}
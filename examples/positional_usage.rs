@@ -0,0 +1,70 @@
+use doppleganger::{Doppleganger, Mirror};
+
+// Test forward named -> tuple mirroring
+#[derive(Debug, Doppleganger)]
+#[dg(forward = WireCoord)]
+struct Coord {
+    #[dg(index = 1)]
+    lng: f64,
+    #[dg(index = 0)]
+    lat: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct WireCoord(f64, f64);
+
+// Test backward tuple -> named mirroring
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireCoord)]
+struct Position {
+    #[dg(index = 0)]
+    lat: f64,
+    #[dg(index = 1)]
+    lng: f64,
+}
+
+// Test forward tuple -> named mirroring
+#[derive(Debug, Doppleganger)]
+#[dg(forward = Named)]
+struct Pair(#[dg(rename = "key")] String, #[dg(rename = "value")] u32);
+
+#[derive(Debug, PartialEq)]
+struct Named {
+    key: String,
+    value: u32,
+}
+
+// Test backward named -> tuple mirroring
+#[derive(Debug, Doppleganger)]
+#[dg(backward = Named)]
+struct Entry(#[dg(rename = "key")] String, #[dg(rename = "value")] u32);
+
+fn main() {
+    // Test forward transformation into positional slots
+    let wire = Coord::mirror(Coord { lng: 2.0, lat: 1.0 });
+    assert_eq!(wire, WireCoord(1.0, 2.0));
+    println!("Forward named -> tuple test passed: {:?}", wire);
+
+    // Test backward transformation from positional slots
+    let position = Position::mirror(WireCoord(1.0, 2.0));
+    assert_eq!((position.lat, position.lng), (1.0, 2.0));
+    println!("Backward tuple -> named test passed: {:?}", position);
+
+    // Test forward transformation out of positional slots
+    let named = Pair::mirror(Pair("a".to_string(), 1));
+    assert_eq!(
+        named,
+        Named {
+            key: "a".to_string(),
+            value: 1
+        }
+    );
+    println!("Forward tuple -> named test passed: {:?}", named);
+
+    // Test backward transformation into positional slots
+    let entry = Entry::mirror(named);
+    assert_eq!((entry.0.as_str(), entry.1), ("a", 1));
+    println!("Backward named -> tuple test passed: {:?}", entry);
+
+    println!("All positional tests passed!");
+}
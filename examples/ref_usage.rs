@@ -0,0 +1,125 @@
+use doppleganger::{Doppleganger, Mirror, MirrorRef};
+use std::collections::HashMap;
+
+// Test mirroring from a reference, the source stays usable afterwards
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiUser, ref)]
+struct User {
+    id: u64,
+    #[dg(rename = "username")]
+    name: String,
+    roles: Vec<Role>,
+    manager: Option<String>,
+    settings: HashMap<String, bool>,
+    #[dg(map = |tags: Vec<String>| tags.len())]
+    tags: Vec<String>,
+    #[dg(flatten(city))]
+    address: Address,
+    #[dg(ignore)]
+    password_hash: String,
+}
+
+#[derive(Debug, Clone)]
+struct Address {
+    city: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiUser {
+    id: u64,
+    username: String,
+    roles: Vec<ApiRole>,
+    manager: Option<String>,
+    settings: HashMap<String, bool>,
+    tags: usize,
+    city: String,
+}
+
+// Test enums, matching on the borrowed source
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiRole, ref)]
+enum Role {
+    Admin,
+    Member { team: String },
+    Guest(u32),
+}
+
+#[derive(Debug, PartialEq)]
+enum ApiRole {
+    Admin,
+    Member { team: String },
+    Guest(u32),
+}
+
+// Test the backward direction on a generic type
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WirePage<T::Source>, ref)]
+struct Page<T> {
+    items: Vec<T>,
+    #[dg(default)]
+    cursor: Option<String>,
+}
+
+struct WirePage<T> {
+    items: Vec<T>,
+}
+
+fn main() {
+    // Test the struct mirror keeps the source
+    let user = User {
+        id: 1,
+        name: "alice".to_string(),
+        roles: vec![
+            Role::Admin,
+            Role::Member {
+                team: "core".to_string(),
+            },
+            Role::Guest(7),
+        ],
+        manager: Some("bob".to_string()),
+        settings: HashMap::from([("dark_mode".to_string(), true)]),
+        tags: vec!["a".to_string(), "b".to_string()],
+        address: Address {
+            city: "Paris".to_string(),
+        },
+        password_hash: "hash".to_string(),
+    };
+    let api = User::mirror_ref(&user);
+    assert_eq!(
+        api,
+        ApiUser {
+            id: 1,
+            username: "alice".to_string(),
+            roles: vec![
+                ApiRole::Admin,
+                ApiRole::Member {
+                    team: "core".to_string()
+                },
+                ApiRole::Guest(7),
+            ],
+            manager: Some("bob".to_string()),
+            settings: HashMap::from([("dark_mode".to_string(), true)]),
+            tags: 2,
+            city: "Paris".to_string(),
+        }
+    );
+    assert_eq!(user.name, "alice");
+    assert_eq!(user.password_hash, "hash");
+    println!("Struct ref test passed: {:?}", api);
+
+    // Test the by value mirror is still generated
+    assert_eq!(User::mirror(user), api);
+    println!("By value test passed");
+
+    // Test the generic backward mirror
+    let wire = WirePage {
+        items: vec![1u32, 2, 3],
+    };
+    let page: Page<u32> = Page::mirror_ref(&wire);
+    assert_eq!(page.items, [1, 2, 3]);
+    assert_eq!(page.cursor, None);
+    assert_eq!(wire.items.len(), 3);
+    println!("Generic ref test passed: {:?}", page);
+
+    println!("All MirrorRef tests passed!");
+}
@@ -0,0 +1,118 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom};
+
+// Test a field that is read in but never written out, and one the other way around that
+// is filled from its default
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiUser, backward = SignupForm)]
+struct User {
+    id: u64,
+    name: String,
+    #[dg(skip_forward)]
+    password_hash: String,
+    #[dg(skip_backward, default = 1_700_000_000, rename(for = ApiUser) = "member_since")]
+    created_at: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiUser {
+    id: u64,
+    name: String,
+    member_since: u64,
+}
+
+struct SignupForm {
+    id: u64,
+    name: String,
+    password_hash: String,
+}
+
+// Test skipping in both directions of the same type, the field skipped backward uses its
+// default and the one skipped forward is computed
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(both = Settings, extra(locale = |_: &Config| "en".to_string()))]
+struct Config {
+    theme: String,
+    #[dg(skip_backward, default)]
+    resolved_path: String,
+    #[dg(skip_forward, map = |s: String| s.to_lowercase())]
+    locale: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Settings {
+    theme: String,
+    resolved_path: String,
+    locale: String,
+}
+
+// Test skipping the only direction, filled from the struct default
+#[derive(Debug, Default, Doppleganger)]
+#[dg(backward = WireEvent, default)]
+struct Event {
+    #[dg(skip_backward)]
+    seen: bool,
+    name: String,
+}
+
+struct WireEvent {
+    name: String,
+}
+
+fn main() {
+    // Test the password is read in but not written out
+    let user = User::mirror_from(SignupForm {
+        id: 1,
+        name: "alice".to_string(),
+        password_hash: "hash".to_string(),
+    });
+    assert_eq!(user.password_hash, "hash");
+    assert_eq!(user.created_at, 1_700_000_000);
+    let api = ApiUser::mirror_from(user);
+    assert_eq!(
+        api,
+        ApiUser {
+            id: 1,
+            name: "alice".to_string(),
+            member_since: 1_700_000_000,
+        }
+    );
+    println!("Skip forward test passed: {:?}", api);
+
+    // Test skipping on the same type in both directions
+    let settings = Settings::mirror_from(Config {
+        theme: "dark".to_string(),
+        resolved_path: "/etc/app".to_string(),
+        locale: "en".to_string(),
+    });
+    assert_eq!(
+        settings,
+        Settings {
+            theme: "dark".to_string(),
+            resolved_path: "/etc/app".to_string(),
+            locale: "en".to_string(),
+        }
+    );
+    let config = Config::mirror_from(Settings {
+        theme: "light".to_string(),
+        resolved_path: "/tmp".to_string(),
+        locale: "FR".to_string(),
+    });
+    assert_eq!(
+        config,
+        Config {
+            theme: "light".to_string(),
+            resolved_path: String::new(),
+            locale: "fr".to_string(),
+        }
+    );
+    println!("Skip both directions test passed: {:?}", config);
+
+    let event = Event::mirror(WireEvent {
+        name: "login".to_string(),
+    });
+    assert!(!event.seen);
+    assert_eq!(event.name, "login");
+    println!("Skip single direction test passed: {:?}", event);
+
+    println!("All skip tests passed!");
+}
@@ -0,0 +1,253 @@
+use doppleganger::{Doppleganger, Mirror, MirrorError, TryMirror};
+use std::convert::Infallible;
+use std::fmt;
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq)]
+struct Email(String);
+
+#[derive(Debug, PartialEq)]
+struct InvalidEmail(String);
+
+impl fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid email {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidEmail {}
+
+impl TryMirror for Email {
+    type Source = String;
+    type Dest = Email;
+    type Error = InvalidEmail;
+
+    fn try_mirror(source: String) -> Result<Email, InvalidEmail> {
+        if source.contains('@') {
+            Ok(Email(source))
+        } else {
+            Err(InvalidEmail(source))
+        }
+    }
+}
+
+// Field errors convert into the error of the mirror through From
+#[derive(Debug, PartialEq)]
+enum ValidationError {
+    Email(InvalidEmail),
+    Age(ParseIntError),
+    Handle(String),
+    // containers report a MirrorError
+    Nested(String),
+}
+
+impl From<MirrorError> for ValidationError {
+    fn from(err: MirrorError) -> Self {
+        ValidationError::Nested(err.to_string())
+    }
+}
+
+impl From<InvalidEmail> for ValidationError {
+    fn from(err: InvalidEmail) -> Self {
+        ValidationError::Email(err)
+    }
+}
+
+impl From<ParseIntError> for ValidationError {
+    fn from(err: ParseIntError) -> Self {
+        ValidationError::Age(err)
+    }
+}
+
+impl From<Infallible> for ValidationError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
+
+// Test a fallible backward mirror with a custom error
+#[derive(Debug, Doppleganger)]
+#[dg(try_backward = WireSignup, error = ValidationError)]
+struct Signup {
+    name: String,
+    email: Email,
+    #[dg(try_map = |age: String| age.parse::<u8>())]
+    age: u8,
+    backup_emails: Vec<Email>,
+}
+
+struct WireSignup {
+    name: String,
+    email: String,
+    age: String,
+    backup_emails: Vec<String>,
+}
+
+// Test a fallible forward mirror with the default MirrorError
+#[derive(Debug, Doppleganger)]
+#[dg(try_forward = Narrow)]
+struct Wide {
+    #[dg(try_map = u8::try_from)]
+    low: u32,
+    label: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Narrow {
+    low: u8,
+    label: String,
+}
+
+// Test fallible generic mirrors
+#[derive(Debug, Doppleganger)]
+#[dg(try_backward = WireList<T::Source>, error = ValidationError)]
+struct List<T> {
+    items: Vec<T>,
+}
+
+struct WireList<T> {
+    items: Vec<T>,
+}
+
+// Test fallible enums
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(try_backward = WireContact, error = ValidationError)]
+enum Contact {
+    Email(Email),
+    Phone { number: String },
+}
+
+enum WireContact {
+    Email(String),
+    Phone { number: String },
+}
+
+// Test an infallible and a fallible direction on the same type
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiAccount, try_backward = WireAccount, error = ValidationError)]
+struct Account {
+    id: u64,
+    #[dg(try_map = parse_handle)]
+    handle: String,
+}
+
+fn parse_handle(handle: String) -> Result<String, ValidationError> {
+    match handle.strip_prefix('@') {
+        Some(handle) => Ok(handle.to_string()),
+        None => Err(ValidationError::Handle(handle)),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiAccount {
+    id: u64,
+    handle: String,
+}
+
+struct WireAccount {
+    id: u64,
+    handle: String,
+}
+
+fn main() {
+    // Test the backward fallible mirror
+    let signup = Signup::try_mirror(WireSignup {
+        name: "Alice".to_string(),
+        email: "alice@x.io".to_string(),
+        age: "31".to_string(),
+        backup_emails: vec!["a@y.io".to_string()],
+    })
+    .unwrap();
+    assert_eq!(signup.name, "Alice");
+    assert_eq!(signup.email, Email("alice@x.io".to_string()));
+    assert_eq!(signup.age, 31);
+    assert_eq!(signup.backup_emails, [Email("a@y.io".to_string())]);
+    let invalid = Signup::try_mirror(WireSignup {
+        name: "Bob".to_string(),
+        email: "bob@x.io".to_string(),
+        age: "31".to_string(),
+        backup_emails: vec!["nope".to_string()],
+    });
+    assert_eq!(
+        invalid.unwrap_err(),
+        ValidationError::Nested(r#"[0]: invalid email "nope""#.to_string())
+    );
+    let invalid = Signup::try_mirror(WireSignup {
+        name: "Carol".to_string(),
+        email: "carol@x.io".to_string(),
+        age: "old".to_string(),
+        backup_emails: Vec::new(),
+    });
+    assert!(matches!(invalid, Err(ValidationError::Age(_))));
+    println!("Fallible backward test passed: {:?}", signup);
+
+    // Test the forward fallible mirror
+    let narrow = Wide::try_mirror(Wide {
+        low: 200,
+        label: "ok".to_string(),
+    })
+    .unwrap();
+    assert_eq!(
+        narrow,
+        Narrow {
+            low: 200,
+            label: "ok".to_string()
+        }
+    );
+    let too_wide = Wide::try_mirror(Wide {
+        low: 300,
+        label: "too wide".to_string(),
+    });
+    assert_eq!(too_wide.unwrap_err().failures()[0].location(), "low");
+    println!("Fallible forward test passed: {:?}", narrow);
+
+    // Test the generic fallible mirror
+    let list: List<Email> = List::try_mirror(WireList {
+        items: vec!["a@x.io".to_string()],
+    })
+    .unwrap();
+    assert_eq!(list.items, [Email("a@x.io".to_string())]);
+    println!("Fallible generic test passed: {:?}", list);
+
+    // Test the fallible enum mirror
+    assert_eq!(
+        Contact::try_mirror(WireContact::Email("a@x.io".to_string())),
+        Ok(Contact::Email(Email("a@x.io".to_string())))
+    );
+    assert_eq!(
+        Contact::try_mirror(WireContact::Phone {
+            number: "555".to_string()
+        }),
+        Ok(Contact::Phone {
+            number: "555".to_string()
+        })
+    );
+    assert!(Contact::try_mirror(WireContact::Email("bad".to_string())).is_err());
+    println!("Fallible enum test passed");
+
+    // Test mixing an infallible and a fallible direction
+    let account = Account::try_mirror(WireAccount {
+        id: 1,
+        handle: "@alice".to_string(),
+    })
+    .unwrap();
+    assert_eq!(account.handle, "alice");
+    assert_eq!(
+        Account::mirror(account),
+        ApiAccount {
+            id: 1,
+            handle: "alice".to_string()
+        }
+    );
+    assert_eq!(
+        Account::try_mirror(WireAccount {
+            id: 2,
+            handle: "bob".to_string(),
+        })
+        .unwrap_err(),
+        ValidationError::Handle("bob".to_string())
+    );
+    println!("Mixed directions test passed");
+
+    println!("All fallible derive tests passed!");
+}
@@ -0,0 +1,130 @@
+use doppleganger::{AsTryMirror, Doppleganger, Mirror, TryMirror};
+use std::{collections::HashMap, fmt};
+
+// A domain newtype that rejects invalid wire strings
+#[derive(Debug, PartialEq)]
+struct Email(String);
+
+#[derive(Debug, PartialEq)]
+struct InvalidEmail(String);
+
+impl fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid email {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidEmail {}
+
+impl TryMirror for Email {
+    type Source = String;
+    type Dest = Email;
+    type Error = InvalidEmail;
+
+    fn try_mirror(source: String) -> Result<Email, InvalidEmail> {
+        if source.contains('@') {
+            Ok(Email(source))
+        } else {
+            Err(InvalidEmail(source))
+        }
+    }
+}
+
+// A hand written infallible Mirror
+#[derive(Debug, PartialEq)]
+struct Cents(i64);
+
+impl Mirror for Cents {
+    type Source = f64;
+    type Dest = Cents;
+
+    fn mirror(source: f64) -> Cents {
+        Cents((source * 100.0).round() as i64)
+    }
+}
+
+// Derived infallible types implement TryMirror as well
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiUser)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiUser {
+    id: u64,
+    name: String,
+}
+
+fn main() {
+    // Test a fallible conversion
+    assert_eq!(
+        Email::try_mirror("a@example.com".to_string()),
+        Ok(Email("a@example.com".to_string()))
+    );
+    assert_eq!(
+        Email::try_mirror("nope".to_string()),
+        Err(InvalidEmail("nope".to_string()))
+    );
+    println!("TryMirror test passed");
+
+    // Test containers, the first failure is reported with its index or key
+    let emails = Vec::<Email>::try_mirror(vec!["a@x.io".to_string(), "b@x.io".to_string()]);
+    assert_eq!(
+        emails.unwrap(),
+        [Email("a@x.io".to_string()), Email("b@x.io".to_string())]
+    );
+    let emails = Vec::<Email>::try_mirror(vec![
+        "a@x.io".to_string(),
+        "bad".to_string(),
+        "worse".to_string(),
+    ]);
+    let err = emails.unwrap_err();
+    assert_eq!(err.failures().len(), 1);
+    assert_eq!(err.failures()[0].location(), "[1]");
+    assert!(err.failures()[0].error().is::<InvalidEmail>());
+
+    // Test asking a container for every failure
+    let emails = Vec::<Email>::try_mirror_all(vec![
+        "a@x.io".to_string(),
+        "bad".to_string(),
+        "worse".to_string(),
+    ]);
+    assert_eq!(
+        emails.unwrap_err().to_string(),
+        r#"[1]: invalid email "bad"; [2]: invalid email "worse""#
+    );
+    assert_eq!(Option::<Email>::try_mirror(None).unwrap(), None);
+    let by_name = HashMap::<String, Email>::try_mirror(HashMap::from([(
+        "alice".to_string(),
+        "alice@x.io".to_string(),
+    )]));
+    assert_eq!(by_name.unwrap()["alice"], Email("alice@x.io".to_string()));
+    let by_name = HashMap::<String, Email>::try_mirror(HashMap::from([(
+        "bob".to_string(),
+        "bob".to_string(),
+    )]));
+    let err = by_name.unwrap_err();
+    assert_eq!(err.failures()[0].location(), r#"["bob"]"#);
+    assert!(err.failures()[0].error().is::<InvalidEmail>());
+    println!("Container TryMirror test passed");
+
+    // Test the bridges from infallible conversions
+    assert_eq!(u32::try_mirror(7), Ok(7));
+    assert_eq!(AsTryMirror::<Cents>::try_mirror(1.25), Ok(Cents(125)));
+    let user = User::try_mirror(User {
+        id: 1,
+        name: "Alice".to_string(),
+    });
+    assert_eq!(
+        user,
+        Ok(ApiUser {
+            id: 1,
+            name: "Alice".to_string()
+        })
+    );
+    println!("Infallible bridge test passed");
+
+    println!("All TryMirror tests passed!");
+}
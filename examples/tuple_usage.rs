@@ -0,0 +1,56 @@
+use doppleganger::{Doppleganger, Mirror};
+use std::sync::Arc;
+
+// Test forward newtype mirroring
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiUserId)]
+struct UserId(u64);
+
+#[derive(Debug, PartialEq)]
+struct ApiUserId(u64);
+
+// Test forward tuple mirroring with ignored fields
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiPoint)]
+struct Point(i32, #[dg(ignore)] bool, i32);
+
+#[derive(Debug, PartialEq)]
+struct ApiPoint(i32, i32);
+
+// Test backward tuple mirroring with a map
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireTag)]
+struct Tag(#[dg(map = Arc::from)] Arc<str>, Vec<UserIdDto>);
+
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireUserId)]
+struct UserIdDto(u64);
+
+struct WireUserId(u64);
+
+struct WireTag(String, Vec<WireUserId>);
+
+fn main() {
+    // Test forward transformation of a newtype
+    let id = UserId::mirror(UserId(7));
+    assert_eq!(id, ApiUserId(7));
+    println!("Forward newtype test passed: {:?}", id);
+
+    // Test forward transformation skipping an ignored field
+    let point = Point(1, true, 2);
+    assert!(point.1);
+    let point = Point::mirror(point);
+    assert_eq!(point, ApiPoint(1, 2));
+    println!("Forward tuple test passed: {:?}", point);
+
+    // Test backward transformation with a map
+    let tag = Tag::mirror(WireTag(
+        "rust".to_string(),
+        vec![WireUserId(1), WireUserId(2)],
+    ));
+    assert_eq!(tag.0.as_ref(), "rust");
+    assert_eq!(tag.1.iter().map(|id| id.0).collect::<Vec<_>>(), [1, 2]);
+    println!("Backward tuple test passed: {:?}", tag);
+
+    println!("All tuple tests passed!");
+}
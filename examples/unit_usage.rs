@@ -0,0 +1,42 @@
+use doppleganger::{Doppleganger, Mirror};
+
+// Test forward unit struct mirroring
+#[derive(Debug, Doppleganger)]
+#[dg(forward = AckDto)]
+struct Ack;
+
+#[derive(Debug, PartialEq)]
+struct AckDto;
+
+// Test backward unit struct mirroring
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(backward = AckDto)]
+struct Received;
+
+// Test forward mirroring into a struct filled from its defaults
+#[derive(Debug, Doppleganger)]
+#[dg(forward = Heartbeat, default)]
+struct Ping;
+
+#[derive(Debug, Default, PartialEq)]
+struct Heartbeat {
+    sequence: u64,
+    note: Option<String>,
+}
+
+fn main() {
+    // Test forward transformation of a marker type
+    assert_eq!(Ack::mirror(Ack), AckDto);
+    println!("Forward unit test passed");
+
+    // Test backward transformation of a marker type
+    assert_eq!(Received::mirror(AckDto), Received);
+    println!("Backward unit test passed");
+
+    // Test forward transformation into a defaulted struct
+    let heartbeat = Ping::mirror(Ping);
+    assert_eq!(heartbeat, Heartbeat::default());
+    println!("Default unit test passed: {:?}", heartbeat);
+
+    println!("All unit tests passed!");
+}
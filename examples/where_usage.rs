@@ -0,0 +1,78 @@
+use doppleganger::{Doppleganger, Mirror};
+
+// Test forward mirroring of a struct with a where clause
+#[derive(Debug, Doppleganger)]
+#[dg(forward = Summary)]
+struct Report<T>
+where
+    T: ::std::fmt::Display,
+{
+    name: String,
+    #[dg(ignore)]
+    payload: T,
+}
+
+#[derive(Debug, PartialEq)]
+struct Summary {
+    name: String,
+}
+
+// Test backward mirroring of a tuple struct with a higher-ranked bound
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireCounter)]
+struct Counter<T>(u32, #[dg(default)] T)
+where
+    for<'a> &'a T: Into<String>,
+    T: Default;
+
+struct WireCounter(u32);
+
+// Test enums with a where clause
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiOutcome)]
+enum Outcome<E>
+where
+    E: Clone,
+{
+    Done,
+    Failed(u32, #[dg(ignore)] E),
+}
+
+#[derive(Debug, PartialEq)]
+enum ApiOutcome {
+    Done,
+    Failed(u32),
+}
+
+fn main() {
+    // Test forward transformation with a where clause
+    let report = Report {
+        name: "weekly".to_string(),
+        payload: 42,
+    };
+    println!("Report payload: {}", report.payload);
+    let summary = Report::mirror(report);
+    assert_eq!(
+        summary,
+        Summary {
+            name: "weekly".to_string()
+        }
+    );
+    println!("Forward where test passed: {:?}", summary);
+
+    // Test backward transformation with a higher-ranked bound
+    let counter: Counter<String> = Counter::mirror(WireCounter(3));
+    assert_eq!(counter.0, 3);
+    assert_eq!(Into::<String>::into(&counter.1), "");
+    println!("Backward where test passed: {:?}", counter);
+
+    // Test enum transformation with a where clause
+    assert_eq!(Outcome::<String>::mirror(Outcome::Done), ApiOutcome::Done);
+    assert_eq!(
+        Outcome::mirror(Outcome::Failed(2, "boom".to_string())),
+        ApiOutcome::Failed(2)
+    );
+    println!("Enum where test passed");
+
+    println!("All where clause tests passed!");
+}
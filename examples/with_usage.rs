@@ -0,0 +1,103 @@
+use doppleganger::{Doppleganger, Mirror, MirrorFrom};
+use std::time::Duration;
+
+// Reusable converters, each module exposes a `forward` and a `backward` fn
+mod conv {
+    pub mod cents {
+        pub fn forward(amount: f64) -> i64 {
+            (amount * 100.0).round() as i64
+        }
+
+        pub fn backward(cents: i64) -> f64 {
+            cents as f64 / 100.0
+        }
+    }
+
+    pub mod seconds {
+        use std::time::Duration;
+
+        pub fn forward(duration: Duration) -> u64 {
+            duration.as_secs()
+        }
+
+        pub fn backward(seconds: u64) -> Duration {
+            Duration::from_secs(seconds)
+        }
+    }
+}
+
+// Test a module converter in both directions
+#[derive(Debug, PartialEq, Doppleganger)]
+#[dg(both = DbProduct)]
+struct Product {
+    name: String,
+    #[dg(with = conv::cents)]
+    price: f64,
+    #[dg(rename = "ttl_seconds", with = conv::seconds)]
+    ttl: Duration,
+}
+
+#[derive(Debug, PartialEq)]
+struct DbProduct {
+    name: String,
+    price: i64,
+    ttl_seconds: u64,
+}
+
+// Test the same converters on single direction derives
+#[derive(Debug, Doppleganger)]
+#[dg(forward = ApiFee)]
+struct Fee {
+    #[dg(with = crate::conv::cents)]
+    amount: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiFee {
+    amount: i64,
+}
+
+#[derive(Debug, Doppleganger)]
+#[dg(backward = WireSession)]
+struct Session {
+    #[dg(with = conv::seconds)]
+    timeout: Duration,
+}
+
+struct WireSession {
+    timeout: u64,
+}
+
+fn main() {
+    // Test both directions
+    let product = Product {
+        name: "Widget".to_string(),
+        price: 9.99,
+        ttl: Duration::from_secs(60),
+    };
+    let db_product = DbProduct::mirror_from(product);
+    assert_eq!(
+        db_product,
+        DbProduct {
+            name: "Widget".to_string(),
+            price: 999,
+            ttl_seconds: 60,
+        }
+    );
+    let product = Product::mirror_from(db_product);
+    assert_eq!(product.price, 9.99);
+    assert_eq!(product.ttl, Duration::from_secs(60));
+    println!("With both directions test passed: {:?}", product);
+
+    // Test forward only
+    let fee = Fee::mirror(Fee { amount: 1.5 });
+    assert_eq!(fee, ApiFee { amount: 150 });
+    println!("With forward test passed: {:?}", fee);
+
+    // Test backward only
+    let session = Session::mirror(WireSession { timeout: 30 });
+    assert_eq!(session.timeout, Duration::from_secs(30));
+    println!("With backward test passed: {:?}", session);
+
+    println!("All with tests passed!");
+}